@@ -1,4 +1,4 @@
-use regex::Regex;
+use advent_of_code::parse;
 
 advent_of_code::solution!(3);
 
@@ -20,31 +20,29 @@ impl Program {
     }
 
     fn parse(&mut self) {
-        let regex = Regex::new(r"mul\(\d{1,3},\d{1,3}\)").unwrap();
-        for caps in regex.find_iter(&self.input) {
-            let nums = caps
-                .as_str()
-                .split(&['(', ')', ','][..])
-                .collect::<Vec<_>>();
-
-            self.instructions.push((
-                caps.start(),
-                nums[1].parse::<i32>().unwrap(),
-                nums[2].parse::<i32>().unwrap(),
-            ));
+        let number = || {
+            parse::map(parse::take_while(1, 3, |c| c.is_ascii_digit()), |n| {
+                n.parse::<i32>().unwrap()
+            })
+        };
+        let mul = parse::delimited(
+            parse::literal("mul("),
+            parse::pair(parse::terminated(number(), parse::literal(",")), number()),
+            parse::literal(")"),
+        );
+
+        for (idx, (a, b)) in parse::find_all(mul, &self.input) {
+            self.instructions.push((idx, a, b));
         }
     }
 
     fn parse_dos(&mut self) {
-        let regex = Regex::new(r"(do\(\)|don't\(\))").unwrap();
+        let toggle = parse::or(
+            parse::map(parse::literal("do()"), |_| true),
+            parse::map(parse::literal("don't()"), |_| false),
+        );
 
-        for caps in regex.find_iter(&self.input) {
-            if caps.as_str() == "do()" {
-                self.dos.push((caps.start(), true))
-            } else {
-                self.dos.push((caps.start(), false))
-            }
-        }
+        self.dos.extend(parse::find_all(toggle, &self.input));
     }
 
     fn run(&mut self) -> i32 {
@@ -96,7 +94,9 @@ mod day_03 {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples",
+            DAY,
+            advent_of_code::part!(2),
         ));
         assert_eq!(result, Some(48));
     }
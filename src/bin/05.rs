@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
+use advent_of_code::parse;
+
 advent_of_code::solution!(5);
 
 #[allow(dead_code)]
@@ -11,26 +13,24 @@ struct Page {
 #[allow(dead_code)]
 impl Page {
     fn new(input: &str) -> Self {
-        let (orders_data, prods_data) = input.split_once("\n\n").unwrap();
+        let [orders_data, prods_data] = parse::sections(input)[..] else {
+            panic!("expected the ordering rules and the updates");
+        };
 
         let orders = orders_data.lines().fold(HashMap::new(), |mut acc, line| {
-            let (before, after) = line.split_once("|").unwrap();
-            let before = before.parse::<usize>().unwrap();
-            let after = after.parse::<usize>().unwrap();
+            let mut pages = parse::unsigned(line);
+            let before = pages.next().unwrap();
+            let after = pages.next().unwrap();
 
             acc.entry(before).or_insert_with(HashSet::new).insert(after);
 
             acc
         });
 
-        let prods = prods_data.lines().fold(Vec::new(), |mut acc, line| {
-            let update = line
-                .split(",")
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-            acc.push(update);
-            acc
-        });
+        let prods = prods_data
+            .lines()
+            .map(|line| parse::unsigned(line).collect())
+            .collect();
 
         Self {
             orders,
@@ -1,211 +1,116 @@
 use std::collections::HashSet;
 
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::viz;
+
 advent_of_code::solution!(6);
 
-#[allow(dead_code)]
 struct Map {
-    width: i32,
-    height: i32,
-    data: Vec<Vec<char>>,
-    start_pos: (i32, i32),
+    grid: Grid<char>,
+    start_pos: Point,
 }
 
-#[allow(dead_code)]
 impl Map {
     fn new(input: &str) -> Self {
-        let mut start_pos = (0, 0);
-
-        let data =
-            input
-                .lines()
-                .enumerate()
-                .fold(Vec::<Vec<char>>::new(), |mut acc, (idx, line)| {
-                    let mut row = vec![];
-
-                    line.chars().enumerate().for_each(|(jdx, mut c)| {
-                        if c == '^' {
-                            start_pos = (idx as i32, jdx as i32);
-                            c = '.';
-                        }
-                        row.push(c)
-                    });
-                    acc.push(row);
-                    acc
-                });
-
-        Map {
-            width: data[0].len() as i32,
-            height: data.len() as i32,
-            data,
-            start_pos,
-        }
-    }
+        let mut grid = Grid::parse(input);
+        let start_pos = grid
+            .position_of(&'^')
+            .expect("the guard should be on the map");
+        grid[start_pos] = '.';
 
-    fn at(&self, pos: Position) -> Option<char> {
-        if pos.y < 0 || pos.y >= self.height || pos.x < 0 || pos.x >= self.width {
-            None
-        } else {
-            Some(self.data[pos.y as usize][pos.x as usize])
-        }
+        Map { grid, start_pos }
     }
 
-    fn run(&self) -> i32 {
-        let mut pos = Position::new(self.start_pos.1, self.start_pos.0);
-        let mut dir = Direction::new((0, -1));
-        let mut map = self.data.clone();
-
-        map[pos.y as usize][pos.x as usize] = 'X';
+    fn path(&self) -> HashSet<Point> {
+        let mut pos = self.start_pos;
+        let mut dir = Point::UP;
+        let mut visited = HashSet::new();
 
         loop {
-            match self.at(pos.ahead(&dir)) {
-                Some('#') => {
-                    dir.turn_right();
-                    continue;
-                }
+            visited.insert(pos);
+
+            let next = pos + dir;
+            match self.grid.get(next) {
+                Some('#') => dir = dir.turn_right(),
                 None => break,
-                _ => {}
+                _ => pos = next,
             }
-
-            pos.move_to(&dir);
-            map[pos.y as usize][pos.x as usize] = 'X';
         }
 
-        map.iter().flatten().fold(0, |acc, cur| {
-            if *cur == 'X' {
-                return acc + 1;
-            }
-
-            acc
-        })
+        visited
     }
 
-    fn path(&self) -> HashSet<Position> {
-        let mut pos = Position::new(self.start_pos.1, self.start_pos.0);
-        let mut dir = Direction::default();
-        let mut visited = HashSet::new();
+    /// Shows the guard walking its route and the positions it visited as `X`, for `--viz`.
+    fn animate(&self) {
+        let mut grid = self.grid.clone();
+        let mut pos = self.start_pos;
+        let mut dir = Point::UP;
 
-        while self.at(pos).is_some() {
-            visited.insert(pos);
+        loop {
+            grid[pos] = '^';
+            viz::push_chars(&grid);
+            grid[pos] = 'X';
 
-            let next = pos.ahead(&dir);
-            match self.at(next) {
-                Some('#') => {
-                    dir.turn_right();
-                }
+            let next = pos + dir;
+            match self.grid.get(next) {
+                Some('#') => dir = dir.turn_right(),
                 None => break,
-                _ => {
-                    pos = next;
-                }
+                _ => pos = next,
             }
         }
-
-        visited
+        viz::push_chars(&grid);
     }
 
     fn traverse_with_cycle(&self) -> bool {
-        let mut pos = Position::new(self.start_pos.1, self.start_pos.0);
-        let mut dir = Direction::default();
+        let mut pos = self.start_pos;
+        let mut dir = Point::UP;
         let mut seen = HashSet::new();
 
-        while self.at(pos).is_some() {
+        loop {
             if !seen.insert((pos, dir)) {
                 return true;
             }
 
-            let next = pos.ahead(&dir);
-            match self.at(next) {
-                Some('#') => dir.turn_right(),
+            let next = pos + dir;
+            match self.grid.get(next) {
+                Some('#') => dir = dir.turn_right(),
                 None => return false,
                 _ => pos = next,
             }
         }
+    }
 
-        false
+    fn run(&self) -> usize {
+        self.path().len()
     }
 
-    fn run2(&mut self) -> i32 {
+    fn run2(&mut self) -> usize {
         let path = self.path();
         let mut count = 0;
 
         for candidate in path {
-            if candidate == Position::new(self.start_pos.1, self.start_pos.0) {
+            if candidate == self.start_pos {
                 continue; // don’t block start
             }
 
-            self.data[candidate.y as usize][candidate.x as usize] = '#';
+            self.grid[candidate] = '#';
 
             if self.traverse_with_cycle() {
                 count += 1;
             }
 
-            self.data[candidate.y as usize][candidate.x as usize] = '.';
+            self.grid[candidate] = '.';
         }
 
         count
     }
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct Direction {
-    delta: (i32, i32),
-}
-
-impl Default for Direction {
-    fn default() -> Self {
-        Direction { delta: (0, -1) }
-    }
-}
-
-#[allow(dead_code)]
-impl Direction {
-    fn new(delta: (i32, i32)) -> Self {
-        Direction { delta }
-    }
-
-    fn turn_right(&mut self) {
-        let (dx, dy) = self.delta;
-        self.delta = (-dy, dx);
-    }
-}
-
-#[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-#[allow(dead_code)]
-impl Position {
-    fn new(x: i32, y: i32) -> Self {
-        Position { x, y }
-    }
-
-    fn get(&self) -> (i32, i32) {
-        (self.x, self.y)
-    }
-
-    fn set(&mut self, x: i32, y: i32) {
-        self.x = x;
-        self.y = y;
-    }
-
-    fn move_to(&mut self, direction: &Direction) {
-        self.x += direction.delta.0;
-        self.y += direction.delta.1;
-    }
-
-    fn ahead(&self, direction: &Direction) -> Position {
-        Position {
-            x: self.x + direction.delta.0,
-            y: self.y + direction.delta.1,
-        }
-    }
-}
-
 pub fn part_one(input: &str) -> Option<u64> {
     let map = Map::new(input);
+    if viz::enabled() {
+        map.animate();
+    }
     Some(map.run() as u64)
 }
 
@@ -1,3 +1,5 @@
+use advent_of_code::parse;
+
 advent_of_code::solution!(7);
 
 #[allow(dead_code)]
@@ -75,17 +77,11 @@ impl All {
         let equations = input
             .lines()
             .map(|line| {
-                let (left, right) = line.split_once(":").unwrap();
-                let left = left.trim().parse::<u64>().unwrap();
-
-                let right: Vec<u64> = right
-                    .split_whitespace()
-                    .map(|s| s.parse::<u64>().unwrap())
-                    .collect();
+                let mut numbers = parse::unsigned(line);
 
                 Equation {
-                    lhs: left,
-                    rhs: right,
+                    lhs: numbers.next().unwrap(),
+                    rhs: numbers.collect(),
                 }
             })
             .collect();
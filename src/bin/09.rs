@@ -1,6 +1,6 @@
 use std::collections::BinaryHeap;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, naive: [part_one_naive, 1]);
 
 pub fn part_one(input: &str) -> Option<usize> {
     let mut lp: usize = 0; // left pointer
@@ -141,9 +141,45 @@ pub fn part_two(input: &str) -> Option<usize> {
     Some(sum)
 }
 
+/// Lays out every block and moves them one by one from the end into the leftmost gap.
+pub fn part_one_naive(input: &str) -> Option<usize> {
+    let mut blocks: Vec<Option<usize>> = vec![];
+    for (i, c) in input.chars().enumerate() {
+        let id = (i % 2 == 0).then_some(i / 2);
+        blocks.extend(std::iter::repeat_n(id, c.to_digit(10)? as usize));
+    }
+
+    let mut left = 0;
+    let mut right = blocks.len().checked_sub(1)?;
+    while left < right {
+        if blocks[left].is_some() {
+            left += 1;
+        } else if blocks[right].is_none() {
+            right -= 1;
+        } else {
+            blocks.swap(left, right);
+        }
+    }
+
+    Some(
+        blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, id)| id.map(|id| pos * id))
+            .sum(),
+    )
+}
+
 #[cfg(test)]
 mod day_09 {
     use super::*;
+    use advent_of_code::check;
+    use advent_of_code::generate::day09::generate;
+
+    #[test]
+    fn test_part_one_matches_naive() {
+        check::differential(generate, part_one, part_one_naive).run(DAY, "part_one");
+    }
 
     #[test]
     fn test_part_one() {
@@ -1,144 +1,66 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(10);
-
-#[allow(dead_code)]
-#[derive(Debug, Default, Clone)]
-struct Node {
-    val: i64,
-    cur: (i64, i64),
-    start: (i64, i64),
-}
+use advent_of_code::grid::{Grid, Point};
 
-#[allow(dead_code)]
-impl Node {
-    fn new(val: i64, start_node: (i64, i64)) -> Self {
-        Self {
-            val,
-            cur: start_node,
-            start: start_node,
-        }
-    }
-}
+advent_of_code::solution!(10);
 
-#[allow(dead_code)]
-#[derive(Debug, Default)]
-struct Grid {
-    width: i64,
-    height: i64,
-    nodes: Vec<Vec<i64>>,
-    zeros: Vec<Node>,
+struct Map {
+    heights: Grid<u32>,
 }
 
-#[allow(dead_code)]
-impl Grid {
+impl Map {
     fn new(input: &str) -> Self {
-        let nodes: Vec<Vec<i64>> = input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).unwrap() as i64)
-                    .collect()
-            })
-            .collect();
-
-        let height = nodes.len() as i64;
-        let width = nodes[0].len() as i64;
-
-        let mut zeros = Vec::new();
-        for (y, row) in nodes.iter().enumerate() {
-            for (x, &val) in row.iter().enumerate() {
-                if val == 0 {
-                    zeros.push(Node::new(val, (x as i64, y as i64)));
-                }
-            }
-        }
-
         Self {
-            width,
-            height,
-            nodes,
-            zeros,
+            heights: Grid::parse_with(input, |c| c.to_digit(10).unwrap()),
         }
     }
 
-    fn solve(&mut self) -> i64 {
-        let mut scored_nodes: HashSet<(i64, i64, i64, i64)> = HashSet::new();
+    /// Visits the end of every hiking trail, calling `f` with its trailhead and summit.
+    fn trails(&self, mut f: impl FnMut(Point, Point)) {
+        let mut stack: Vec<(Point, Point)> = self
+            .heights
+            .positions(|height| *height == 0)
+            .map(|start| (start, start))
+            .collect();
 
-        while let Some(node) = self.zeros.pop() {
-            if node.val == 9 {
-                scored_nodes.insert((node.start.0, node.start.1, node.cur.0, node.cur.1));
+        while let Some((start, cur)) = stack.pop() {
+            let height = self.heights[cur];
+            if height == 9 {
+                f(start, cur);
                 continue;
             }
 
-            for dir in Self::DIRECTIONS {
-                let mut next = node.clone();
-                next.cur.0 += dir.0;
-                next.cur.1 += dir.1;
-
-                if next.cur.0 < 0
-                    || next.cur.1 < 0
-                    || next.cur.0 >= self.width
-                    || next.cur.1 >= self.height
-                {
-                    continue;
-                }
-
-                next.val = self.nodes[next.cur.1 as usize][next.cur.0 as usize];
-
-                if next.val - node.val == 1 {
-                    self.zeros.push(next)
+            for next in cur.neighbours4() {
+                if self.heights.get(next) == Some(&(height + 1)) {
+                    stack.push((start, next));
                 }
             }
         }
+    }
 
+    fn solve(&self) -> i64 {
+        let mut scored_nodes: HashSet<(Point, Point)> = HashSet::new();
+        self.trails(|start, end| {
+            scored_nodes.insert((start, end));
+        });
         scored_nodes.len() as i64
     }
 
-    fn solve2(&mut self) -> i64 {
+    fn solve2(&self) -> i64 {
         let mut scored_nodes: i64 = 0;
-
-        while let Some(node) = self.zeros.pop() {
-            if node.val == 9 {
-                scored_nodes += 1;
-                continue;
-            }
-
-            for dir in Self::DIRECTIONS {
-                let mut next = node.clone();
-                next.cur.0 += dir.0;
-                next.cur.1 += dir.1;
-
-                if next.cur.0 < 0
-                    || next.cur.1 < 0
-                    || next.cur.0 >= self.width
-                    || next.cur.1 >= self.height
-                {
-                    continue;
-                }
-
-                next.val = self.nodes[next.cur.1 as usize][next.cur.0 as usize];
-
-                if next.val - node.val == 1 {
-                    self.zeros.push(next)
-                }
-            }
-        }
-
+        self.trails(|_, _| scored_nodes += 1);
         scored_nodes
     }
-
-    const DIRECTIONS: [(i64, i64); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let mut grid = Grid::new(input);
-    Some(grid.solve())
+    let map = Map::new(input);
+    Some(map.solve())
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let mut grid = Grid::new(input);
-    Some(grid.solve2())
+    let map = Map::new(input);
+    Some(map.solve2())
 }
 
 #[cfg(test)]
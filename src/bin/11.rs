@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, naive: [part_one_naive, 1]);
 
 fn solve(input: &str, loops: u64) -> u64 {
     let mut memo: HashMap<(u64, u64), u64> = HashMap::new();
@@ -48,9 +48,51 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(solve(input, 75))
 }
 
+/// Applies the rules to the whole row of stones, blink by blink.
+fn simulate(input: &str, blinks: u64) -> u64 {
+    let mut stones: Vec<u64> = input
+        .split_whitespace()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    for _ in 0..blinks {
+        stones = stones
+            .into_iter()
+            .flat_map(|stone| {
+                let digits = stone.to_string();
+                if stone == 0 {
+                    vec![1]
+                } else if digits.len() % 2 == 0 {
+                    let (left, right) = digits.split_at(digits.len() / 2);
+                    vec![left.parse().unwrap(), right.parse().unwrap()]
+                } else {
+                    vec![stone * 2024]
+                }
+            })
+            .collect();
+    }
+    stones.len() as u64
+}
+
+pub fn part_one_naive(input: &str) -> Option<u64> {
+    Some(simulate(input, 25))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::check;
+    use advent_of_code::generate::day11::generate;
+
+    #[test]
+    fn test_blink_matches_simulation() {
+        check::differential(
+            generate,
+            |input| solve(input, 15),
+            |input| simulate(input, 15),
+        )
+        .max_size(8)
+        .run(DAY, "blink");
+    }
 
     #[test]
     fn test_part_one() {
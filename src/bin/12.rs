@@ -1,177 +1,98 @@
-advent_of_code::solution!(12);
-
-#[allow(dead_code)]
-const DIRECTION: [(i64, i64); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+use advent_of_code::grid::{Grid, Point};
 
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
-struct Plot {
-    ch: char,
-    id: Option<i64>,
-}
+advent_of_code::solution!(12);
 
-#[allow(dead_code)]
 #[derive(Debug)]
 struct Garden {
-    x: i64,
-    y: i64,
-    plots: Vec<Vec<Plot>>,
+    plots: Grid<char>,
 }
 
-#[allow(dead_code)]
 impl Garden {
     fn new(input: &str) -> Self {
-        let plots: Vec<Vec<Plot>> = input
-            .lines()
-            .map(|line| line.chars().map(|ch| Plot { ch, id: None }).collect())
-            .collect();
-
         Self {
-            x: plots[0].len() as i64,
-            y: plots.len() as i64,
-            plots,
+            plots: Grid::parse(input),
         }
     }
 
-    fn at(&self, x: i64, y: i64) -> Option<&Plot> {
-        if x < 0 || x >= self.x || y < 0 || y >= self.y {
-            return None;
-        }
-        Some(&self.plots[y as usize][x as usize])
-    }
-
-    fn solve(&mut self) -> i64 {
-        let mut sum = 0;
-        let mut region_id = 0;
+    /// Splits the garden into regions of connected plots growing the same plant.
+    fn regions(&self) -> Vec<Vec<Point>> {
+        let mut seen = Grid::new(self.plots.width(), self.plots.height(), false);
+        let mut regions = vec![];
 
-        for y in 0..self.y {
-            for x in 0..self.x {
-                let plot = self.at(x, y);
-
-                if plot.is_none() || plot.unwrap().id.is_some() {
-                    continue;
-                }
-
-                let mut queue: Vec<(i64, i64)> = vec![(x, y)];
-                let mut area = 0;
-                let mut perimeter = 0;
-                region_id += 1;
-
-                while let Some((a, b)) = queue.pop() {
-                    let plot = self.at(a, b);
-
-                    if plot.is_none() || plot.unwrap().id.is_some() {
-                        continue;
-                    }
+        for start in self.plots.points() {
+            if seen[start] {
+                continue;
+            }
 
-                    let ch = plot.unwrap().ch;
-                    self.plots[b as usize][a as usize].id = Some(region_id);
-                    area += 1;
-
-                    for (dx, dy) in DIRECTION.iter() {
-                        let nx = a + dx;
-                        let ny = b + dy;
-
-                        if let Some(neighbor) = self.at(nx, ny) {
-                            if neighbor.ch == ch {
-                                queue.push((nx, ny));
-                            } else {
-                                perimeter += 1;
-                            }
-                        } else {
-                            perimeter += 1;
-                        }
+            let ch = self.plots[start];
+            let mut queue = vec![start];
+            let mut region = vec![];
+            seen[start] = true;
+
+            while let Some(pos) = queue.pop() {
+                region.push(pos);
+                for next in self.plots.neighbours4(pos) {
+                    if !seen[next] && self.plots[next] == ch {
+                        seen[next] = true;
+                        queue.push(next);
                     }
                 }
-
-                sum += area * perimeter;
             }
+
+            regions.push(region);
         }
 
-        sum
+        regions
     }
 
-    fn solve2(&mut self) -> i64 {
-        let mut sum = 0;
-        let mut region_id = 0;
-
-        for y in 0..self.y {
-            for x in 0..self.x {
-                let plot = self.at(x, y);
-
-                if plot.is_none() || plot.unwrap().id.is_some() {
-                    continue;
-                }
-
-                let mut queue: Vec<(i64, i64)> = vec![(x, y)];
-                let mut sides: Vec<Vec<(i64, i64)>> = vec![vec![]; 4];
-                let mut area = 0;
-                region_id += 1;
-
-                while let Some((a, b)) = queue.pop() {
-                    let plot = self.at(a, b);
-
-                    if plot.is_none() || plot.unwrap().id.is_some() {
-                        continue;
-                    }
-
-                    let ch = plot.unwrap().ch;
-                    self.plots[b as usize][a as usize].id = Some(region_id);
-                    area += 1;
-
-                    for (dir, (dx, dy)) in DIRECTION.iter().enumerate() {
-                        let nx = a + dx;
-                        let ny = b + dy;
-
-                        if let Some(neighbor) = self.at(nx, ny) {
-                            if neighbor.ch == ch {
-                                queue.push((nx, ny));
-                                continue;
-                            }
-                        }
-                        if dir < 2 {
-                            sides[dir].push((a, b));
-                        } else {
-                            sides[dir].push((b, a));
-                        }
-                    }
-                }
-
-                for side in sides.iter_mut() {
-                    side.sort_by(|a, b| {
-                        if a.1 == b.1 {
-                            a.0.cmp(&b.0)
-                        } else {
-                            a.1.cmp(&b.1)
-                        }
-                    });
-                }
-
-                let mut sides_c = 0;
-
-                for side in sides.iter() {
-                    for i in 0..side.len() {
-                        if i == 0 || side[i].0 != side[i - 1].0 + 1 || side[i].1 != side[i - 1].1 {
-                            sides_c += 1;
-                        }
-                    }
-                }
+    /// Checks whether a fence separates `pos` from its neighbour in `dir`.
+    fn is_fenced(&self, pos: Point, dir: Point) -> bool {
+        self.plots.get(pos + dir) != self.plots.get(pos)
+    }
 
-                sum += area * sides_c;
-            }
-        }
+    fn solve(&self) -> i64 {
+        self.regions()
+            .iter()
+            .map(|region| {
+                let perimeter = region
+                    .iter()
+                    .flat_map(|pos| Point::ORTHOGONAL.map(|dir| (*pos, dir)))
+                    .filter(|(pos, dir)| self.is_fenced(*pos, *dir))
+                    .count();
+                (region.len() * perimeter) as i64
+            })
+            .sum()
+    }
 
-        sum
+    fn solve2(&self) -> i64 {
+        self.regions()
+            .iter()
+            .map(|region| {
+                // NOTE: a side is counted at its last fence, which has no fenced plot of the
+                // same region next to it along the side.
+                let sides = region
+                    .iter()
+                    .flat_map(|pos| Point::ORTHOGONAL.map(|dir| (*pos, dir)))
+                    .filter(|(pos, dir)| self.is_fenced(*pos, *dir))
+                    .filter(|(pos, dir)| {
+                        let along = *pos + dir.turn_right();
+                        self.plots.get(along) != self.plots.get(*pos)
+                            || !self.is_fenced(along, *dir)
+                    })
+                    .count();
+                (region.len() * sides) as i64
+            })
+            .sum()
     }
 }
 
 pub fn part_one(_input: &str) -> Option<i64> {
-    let mut garden = Garden::new(_input);
+    let garden = Garden::new(_input);
     Some(garden.solve())
 }
 
 pub fn part_two(_input: &str) -> Option<i64> {
-    let mut garden = Garden::new(_input);
+    let garden = Garden::new(_input);
     Some(garden.solve2())
 }
 
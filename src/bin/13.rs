@@ -1,3 +1,5 @@
+use advent_of_code::parse;
+
 advent_of_code::solution!(13);
 
 #[derive(Debug)]
@@ -31,16 +33,14 @@ fn solve_eq(eq1: &Equation, eq2: &Equation) -> Option<(i64, i64)> {
 }
 
 fn solve(input: &str, offset: i64) -> Option<i64> {
-    let sum: i64 = input
-        .split("\n\n")
+    let sum: i64 = parse::sections(input)
+        .into_iter()
         .map(|machine| {
             let mac: Vec<(i64, i64)> = machine
                 .lines()
                 .map(|line| {
-                    let mut parts = line.split(&[',', '+', '='][..]).rev();
-                    let second = parts.next().map(|s| s.parse::<i64>().unwrap());
-                    let first = parts.nth(1).map(|s| s.parse::<i64>().unwrap());
-                    (first.unwrap(), second.unwrap())
+                    let mut parts = parse::unsigned(line);
+                    (parts.next().unwrap(), parts.next().unwrap())
                 })
                 .collect();
 
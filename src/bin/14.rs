@@ -1,7 +1,20 @@
 use std::{cmp::Ordering, ops::Div};
 
+use advent_of_code::{
+    grid::{Grid, Point},
+    parse, trace, viz,
+};
+
 advent_of_code::solution!(14);
 
+/// How many standard deviations the spread of the tree's frame should lie below the others' for
+/// it to clearly stand out.
+const MIN_CONFIDENCE: f64 = 4.0;
+
+/// Size of the floor in the puzzle, the example's is 11×7.
+const WIDTH: i64 = 101;
+const HEIGHT: i64 = 103;
+
 #[derive(Debug)]
 struct Robot {
     x: i64,
@@ -9,13 +22,27 @@ struct Robot {
     dir: (i64, i64),
 }
 
+impl Robot {
+    fn at(&self, width: i64, height: i64, timer: i64) -> (i64, i64) {
+        (
+            (self.x + self.dir.0 * timer).rem_euclid(width),
+            (self.y + self.dir.1 * timer).rem_euclid(height),
+        )
+    }
+}
+
+/// The frame where the robots gather, and how clearly it stands out of the others.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Tree {
+    seconds: i64,
+    confidence: f64,
+}
+
 fn read_input(input: &str) -> Vec<Robot> {
     input
         .lines()
         .map(|line| {
-            let mut parts = line
-                .split(&['=', ',', ' '][..])
-                .filter_map(|s| s.parse::<i64>().ok());
+            let mut parts = parse::signed(line);
             Robot {
                 x: parts.next().unwrap(),
                 y: parts.next().unwrap(),
@@ -29,8 +56,7 @@ fn solve(robots: &[Robot], width: i64, height: i64, timer: i64) -> i64 {
     robots
         .iter()
         .fold([0; 4], |mut acc, robot| {
-            let x = (robot.x + robot.dir.0 * timer).rem_euclid(width);
-            let y = (robot.y + robot.dir.1 * timer).rem_euclid(height);
+            let (x, y) = robot.at(width, height, timer);
 
             match (x.cmp(&width.div(2)), y.cmp(&height.div(2))) {
                 (Ordering::Less, Ordering::Less) => acc[0] += 1,
@@ -46,93 +72,176 @@ fn solve(robots: &[Robot], width: i64, height: i64, timer: i64) -> i64 {
         .product()
 }
 
-fn shift_robots(robots: &mut [Robot], width: i64, height: i64) {
-    robots.iter_mut().for_each(|robot| {
-        robot.x = (robot.x + robot.dir.0).rem_euclid(width);
-        robot.y = (robot.y + robot.dir.1).rem_euclid(height);
-    });
-}
-
-fn variance(robots: &[Robot]) -> i64 {
-    let (sum_x, sum_y) = robots
+fn variance(values: &[i64]) -> f64 {
+    let mean = values.iter().sum::<i64>() as f64 / values.len() as f64;
+    values
         .iter()
-        .fold((0, 0), |(acc_x, acc_y), r| (acc_x + r.x, acc_y + r.y));
+        .map(|v| (*v as f64 - mean).powi(2))
+        .sum::<f64>()
+        / values.len() as f64
+}
 
-    let (mean_x, mean_y) = (sum_x / robots.len() as i64, sum_y / robots.len() as i64);
+/// Finds the time, within one `period`, where the robots are the least spread along one axis.
+/// Returns it with the number of standard deviations its spread lies below the other times'.
+fn axis_minimum(robots: &[Robot], period: i64, axis: impl Fn(&Robot) -> (i64, i64)) -> (i64, f64) {
+    let spreads: Vec<f64> = (0..period)
+        .map(|timer| {
+            let positions: Vec<i64> = robots
+                .iter()
+                .map(|robot| {
+                    let (position, speed) = axis(robot);
+                    (position + speed * timer).rem_euclid(period)
+                })
+                .collect();
+            variance(&positions)
+        })
+        .collect();
 
-    let (variance_x, variance_y) = robots.iter().fold((0, 0), |(var_x, var_y), r| {
-        (var_x + (r.x - mean_x).pow(2), var_y + (r.y - mean_y).pow(2))
-    });
+    let (best, lowest) = spreads
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(timer, spread)| (timer as i64, *spread))
+        .unwrap();
+    let others: Vec<f64> = spreads
+        .iter()
+        .enumerate()
+        .filter(|(timer, _)| *timer as i64 != best)
+        .map(|(_, spread)| *spread)
+        .collect();
+    let mean = others.iter().sum::<f64>() / others.len() as f64;
+    let deviation =
+        (others.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / others.len() as f64).sqrt();
+    let confidence = if deviation > 0.0 {
+        (mean - lowest) / deviation
+    } else {
+        0.0
+    };
 
-    variance_x + variance_y
+    (best, confidence)
 }
 
-#[allow(dead_code)]
-fn shift_robots_by(robots: &mut [Robot], width: i64, height: i64, times: i64) {
-    robots.iter_mut().for_each(|robot| {
-        robot.x = (robot.x + robot.dir.0 * times).rem_euclid(width);
-        robot.y = (robot.y + robot.dir.1 * times).rem_euclid(height);
-    });
+/// Robots move along x with a period of `width` seconds and along y with a period of `height`,
+/// so the tree shows when both axes are at their least spread. The Chinese remainder theorem
+/// combines the two times into one within `width`×`height` seconds, `None` if they never meet.
+fn find_tree(robots: &[Robot], width: i64, height: i64) -> Option<Tree> {
+    let (x_seconds, x_confidence) = axis_minimum(robots, width, |r| (r.x, r.dir.0));
+    let (y_seconds, y_confidence) = axis_minimum(robots, height, |r| (r.y, r.dir.1));
+
+    let seconds = (0..height)
+        .map(|k| x_seconds + k * width)
+        .find(|seconds| seconds % height == y_seconds)?;
+    Some(Tree {
+        seconds,
+        confidence: x_confidence.min(y_confidence),
+    })
 }
 
-#[allow(dead_code)]
-fn print_robots(robots: &[Robot], width: i64, height: i64) {
-    let mut grid = vec![vec!["\x1b[32m█"; width as usize]; height as usize];
-    robots.iter().for_each(|robot| {
-        grid[robot.y as usize][robot.x as usize] = " ";
-    });
-    for row in grid {
-        for cell in row {
-            print!("{}", cell);
-        }
-        println!();
+/// Draws the robots as `█` on the floor.
+fn floor(robots: &[Robot], width: i64, height: i64, timer: i64) -> Grid<char> {
+    let mut grid = Grid::new(width as usize, height as usize, ' ');
+    for robot in robots {
+        let (x, y) = robot.at(width, height, timer);
+        grid[Point::new(x as i32, y as i32)] = '█';
     }
+    grid
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
     let robots = read_input(input);
-
-    if robots.len() < 20 {
-        Some(solve(&robots, 11, 7, 100))
-    } else {
-        Some(solve(&robots, 101, 103, 100))
-    }
+    Some(solve(&robots, WIDTH, HEIGHT, 100))
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let mut robots = read_input(input);
-    let mut it: i64 = 0;
-    let mut sta_dev: i64 = variance(&robots);
-
-    for i in 1..10000 {
-        shift_robots(&mut robots, 101, 103);
-        let cur_sta_dev = variance(&robots);
-        if cur_sta_dev < sta_dev {
-            sta_dev = cur_sta_dev;
-            it = i;
-        }
+    let robots = read_input(input);
+    let tree = find_tree(&robots, WIDTH, HEIGHT)?;
+
+    trace!(
+        info,
+        "lowest variance after {} seconds, confidence {:.1}",
+        tree.seconds,
+        tree.confidence
+    );
+    if tree.confidence < MIN_CONFIDENCE {
+        eprintln!(
+            "Warning: no frame stands out, the best is only {:.1} standard deviations below the others",
+            tree.confidence
+        );
     }
 
-    let mut origins = read_input(input);
-    shift_robots_by(&mut origins, 101, 103, it);
-    print_robots(&origins, 101, 103);
-
-    Some(it)
+    if viz::enabled() {
+        viz::push_chars(&floor(&robots, WIDTH, HEIGHT, tree.seconds));
+    }
+    Some(tree.seconds)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Robots that gather in a square after `seconds`, scattered at any other time.
+    fn gathering(width: i64, height: i64, seconds: i64) -> Vec<Robot> {
+        let mut state = 17_i64;
+        let mut next = |bound: i64| {
+            state = (state * 1_103_515_245 + 12_345).rem_euclid(1 << 31);
+            (state >> 8) % bound
+        };
+
+        (0..400)
+            .map(|i| {
+                let dir = (next(width - 1) + 1, next(height - 1) + 1);
+                let (x, y) = if i % 2 == 0 {
+                    (width / 2 + next(9), height / 2 + next(9))
+                } else {
+                    (next(width), next(height))
+                };
+                Robot {
+                    x: (x - dir.0 * seconds).rem_euclid(width),
+                    y: (y - dir.1 * seconds).rem_euclid(height),
+                    dir,
+                }
+            })
+            .collect()
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+        let robots = read_input(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(solve(&robots, 11, 7, 100), 12);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5253));
+        // NOTE: a tree on a 31×29 floor, the example's robots never gather.
+        let robots = read_input(&advent_of_code::template::read_file_part(
+            "examples",
+            DAY,
+            advent_of_code::part!(2),
+        ));
+        assert_eq!(find_tree(&robots, 31, 29).unwrap().seconds, 517);
+    }
+
+    #[test]
+    fn test_confidence() {
+        let robots = read_input(&advent_of_code::template::read_file("examples", DAY));
+        let tree = find_tree(&robots, 11, 7).unwrap();
+        assert!(tree.confidence < MIN_CONFIDENCE, "{tree:?}");
+
+        let robots = read_input(&advent_of_code::template::read_file_part(
+            "examples",
+            DAY,
+            advent_of_code::part!(2),
+        ));
+        let tree = find_tree(&robots, 31, 29).unwrap();
+        assert!(tree.confidence >= MIN_CONFIDENCE, "{tree:?}");
+    }
+
+    #[test]
+    fn test_find_tree() {
+        for (width, height, seconds) in [(101, 103, 6_512), (41, 29, 1_000), (30, 45, 81)] {
+            let tree = find_tree(&gathering(width, height, seconds), width, height).unwrap();
+            assert_eq!(tree.seconds, seconds);
+            assert!(tree.confidence >= MIN_CONFIDENCE, "{tree:?}");
+        }
     }
 }
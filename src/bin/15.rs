@@ -1,184 +1,125 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(15);
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::template::inspect::Inspect;
+use advent_of_code::viz;
 
-fn move_(pos: (i64, i64), motion: (i64, i64)) -> (i64, i64) {
-    (pos.0 + motion.0, pos.1 + motion.1)
-}
+advent_of_code::solution!(15; inspect: Inspector);
 
-#[allow(dead_code)]
 struct Warehouse {
-    width: i64,
-    height: i64,
-    grid: Vec<Vec<char>>,
-    motions: Vec<(i64, i64)>,
-    robot: (i64, i64),
+    grid: Grid<char>,
+    motions: Vec<Point>,
+    robot: Point,
 }
 
 impl std::fmt::Display for Warehouse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &cell) in row.iter().enumerate() {
-                if (x as i64, y as i64).eq(&self.robot) {
-                    write!(f, "@")?;
-                } else {
-                    write!(f, "{}", cell)?;
-                }
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        writeln!(f, "{}", self.to_grid())
     }
 }
 
-#[allow(dead_code)]
 impl Warehouse {
     fn new(input: &str) -> Self {
-        let mut robot: (i64, i64) = (0, 0);
         let mut sections = input.split("\n\n");
-        let grid: Vec<Vec<char>> = sections
-            .next()
-            .unwrap()
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, c)| {
-                        if c == '@' {
-                            robot = (x as i64, y as i64);
-                            return '.';
-                        }
-                        c
-                    })
-                    .collect()
-            })
-            .collect();
-
-        let motions = Self::parse_motion(sections.next());
-
-        Self {
-            width: grid[0].len() as i64,
-            height: grid.len() as i64,
-            grid,
-            robot,
-            motions,
-        }
+        Self::from_map(sections.next().unwrap(), sections.next())
     }
 
     fn new2(input: &str) -> Self {
-        let mut robot: (i64, i64) = (0, 0);
         let mut sections = input.split("\n\n");
-        let grid: Vec<Vec<char>> = sections
+        let map = sections
             .next()
             .unwrap()
             .lines()
-            .enumerate()
-            .map(|(y, line)| {
+            .map(|line| {
                 line.chars()
-                    .enumerate()
-                    .flat_map(|(x, c)| match c {
-                        '.' => ['.', '.'],
+                    .flat_map(|c| match c {
                         '#' => ['#', '#'],
                         'O' => ['[', ']'],
-                        ch => {
-                            if ch == '@' {
-                                robot = (x as i64 * 2, y as i64)
-                            }
-                            ['.', '.']
-                        }
+                        '@' => ['@', '.'],
+                        _ => ['.', '.'],
                     })
-                    .collect()
+                    .collect::<String>()
             })
-            .collect();
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self::from_map(&map, sections.next())
+    }
 
-        let motions = Self::parse_motion(sections.next());
+    fn from_map(map: &str, motions: Option<&str>) -> Self {
+        let mut grid = Grid::parse(map);
+        let robot = grid
+            .position_of(&'@')
+            .expect("the robot should be in the warehouse");
+        grid[robot] = '.';
 
         Self {
-            width: grid[0].len() as i64,
-            height: grid.len() as i64,
             grid,
             robot,
-            motions,
+            motions: Self::parse_motion(motions),
         }
     }
 
-    fn parse_motion(motions: Option<&str>) -> Vec<(i64, i64)> {
+    fn parse_motion(motions: Option<&str>) -> Vec<Point> {
         motions
             .unwrap()
-            .lines()
-            .flat_map(str::chars)
-            .filter_map(|c| match c {
-                '>' => Some((1, 0)),
-                '<' => Some((-1, 0)),
-                '^' => Some((0, -1)),
-                'v' => Some((0, 1)),
-                _ => None,
-            })
+            .chars()
+            .filter_map(Point::from_arrow)
             .collect()
     }
 
-    fn get(&self, pos: (i64, i64)) -> char {
-        self.grid[pos.1 as usize][pos.0 as usize]
-    }
-
-    fn get_mut(&mut self, pos: (i64, i64)) -> &mut char {
-        &mut self.grid[pos.1 as usize][pos.0 as usize]
+    fn swap(&mut self, pos1: Point, pos2: Point) {
+        let temp = self.grid[pos1];
+        self.grid[pos1] = self.grid[pos2];
+        self.grid[pos2] = temp;
     }
 
-    fn swap(&mut self, pos1: (i64, i64), pos2: (i64, i64)) {
-        let temp = self.get(pos1);
-        *self.get_mut(pos1) = self.get(pos2);
-        *self.get_mut(pos2) = temp;
-    }
-
-    fn attempt(&mut self, motion: (i64, i64)) {
+    fn attempt(&mut self, motion: Point) {
         let robot = self.robot;
-        let mut pos_iter = (1..).map(|i| (robot.0 + motion.0 * i, robot.1 + motion.1 * i));
+        let mut pos_iter = (1..).map(|i| robot + motion * i);
 
         let mut np = pos_iter.next().unwrap();
 
-        if self.get(np) == '#' {
+        if self.grid[np] == '#' {
             return;
         }
 
-        while self.get(np) == 'O' {
+        while self.grid[np] == 'O' {
             np = pos_iter.next().unwrap();
-            if self.get(np) == '#' {
+            if self.grid[np] == '#' {
                 return;
             }
         }
 
-        *self.get_mut(np) = 'O';
-        self.robot = move_(self.robot, motion);
-        *self.get_mut(self.robot) = '.';
+        self.grid[np] = 'O';
+        self.robot += motion;
+        self.grid[self.robot] = '.';
     }
 
-    fn attempt2(&mut self, motion: (i64, i64)) {
-        let next_pos = move_(self.robot, motion);
+    fn attempt2(&mut self, motion: Point) {
+        let next_pos = self.robot + motion;
 
-        match self.get(next_pos) {
+        match self.grid[next_pos] {
             '.' => {
                 self.robot = next_pos;
             }
             '#' => {}
             '[' | ']' => {
-                if motion.1 == 0 {
+                if motion.y == 0 {
                     let mut shifts = vec![next_pos];
 
-                    while let '[' | ']' = self.get(move_(*shifts.last().unwrap(), motion)) {
-                        shifts.push(move_(*shifts.last().unwrap(), motion));
+                    while let '[' | ']' = self.grid[*shifts.last().unwrap() + motion] {
+                        shifts.push(*shifts.last().unwrap() + motion);
                     }
-                    shifts.push(move_(*shifts.last().unwrap(), motion));
+                    shifts.push(*shifts.last().unwrap() + motion);
 
-                    match self.get(*shifts.last().unwrap()) {
+                    match self.grid[*shifts.last().unwrap()] {
                         '#' => {}
                         '.' => {
                             for i in (1..shifts.len()).rev() {
                                 self.swap(shifts[i - 1], shifts[i]);
                             }
                             self.swap(self.robot, shifts[0]);
-                            self.robot = move_(self.robot, motion);
+                            self.robot += motion;
                         }
                         _ => {}
                     }
@@ -192,10 +133,10 @@ impl Warehouse {
                     let mut next_layer = HashSet::new();
 
                     for &pos in shifts.last().unwrap() {
-                        match self.get((pos.0 + motion.0, pos.1 + motion.1)) {
+                        match self.grid[pos + motion] {
                             '.' => {}
                             '[' | ']' => {
-                                next_layer.insert(move_(pos, motion));
+                                next_layer.insert(pos + motion);
                             }
                             '#' => break 'stack,
                             _ => unreachable!(),
@@ -205,7 +146,7 @@ impl Warehouse {
                     if next_layer.is_empty() {
                         for layer in shifts.iter().rev() {
                             for &pos in layer {
-                                self.swap(pos, move_(pos, motion));
+                                self.swap(pos, pos + motion);
                             }
                         }
 
@@ -214,9 +155,9 @@ impl Warehouse {
                     }
 
                     next_layer.clone().iter().for_each(|&i| {
-                        let p = match self.get(i) {
-                            '[' => (i.0 + 1, i.1),
-                            ']' => (i.0 - 1, i.1),
+                        let p = match self.grid[i] {
+                            '[' => i + Point::RIGHT,
+                            ']' => i + Point::LEFT,
                             _ => unreachable!(),
                         };
                         next_layer.insert(p);
@@ -229,21 +170,123 @@ impl Warehouse {
         }
     }
 
-    fn exec(&mut self, by: char, f: fn(&mut Self, (i64, i64))) -> Option<u64> {
+    fn exec(&mut self, by: char, f: fn(&mut Self, Point)) -> Option<u64> {
+        // NOTE: drawing a frame takes longer than a move, so it is skipped unless `--viz` is set.
+        if viz::enabled() {
+            viz::push_chars(&self.to_grid());
+        }
         for motion in self.motions.clone() {
             f(self, motion);
+            if viz::enabled() {
+                viz::push_chars(&self.to_grid());
+            }
+        }
+
+        Some(self.gps(by))
+    }
+
+    /// The warehouse with the robot drawn in, e.g. for a frame of `--viz`.
+    fn to_grid(&self) -> Grid<char> {
+        let mut grid = self.grid.clone();
+        grid[self.robot] = '@';
+        grid
+    }
+
+    /// Sums the GPS coordinates of the boxes, located by their `by` cell.
+    fn gps(&self, by: char) -> u64 {
+        self.grid
+            .positions(|cell| *cell == by)
+            .map(|p| p.y as u64 * 100 + p.x as u64)
+            .sum()
+    }
+}
+
+/// Steps through the robot's moves in `cargo repl`.
+struct Inspector {
+    input: String,
+    /// Whether the warehouse of part two is shown.
+    wide: bool,
+    warehouse: Warehouse,
+    steps: usize,
+}
+
+impl Inspector {
+    fn reset(&mut self) {
+        self.warehouse = if self.wide {
+            Warehouse::new2(&self.input)
+        } else {
+            Warehouse::new(&self.input)
+        };
+        self.steps = 0;
+    }
+
+    fn show(&self) -> String {
+        let (by, part) = if self.wide { ('[', 2) } else { ('O', 1) };
+        format!(
+            "{}after {} of {} moves (part {part}), GPS sum {}",
+            self.warehouse,
+            self.steps,
+            self.warehouse.motions.len(),
+            self.warehouse.gps(by)
+        )
+    }
+}
+
+impl Inspect for Inspector {
+    fn new(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+            wide: false,
+            warehouse: Warehouse::new(input),
+            steps: 0,
         }
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("show", "Print the warehouse."),
+            ("step [n]", "Make the next n moves, 1 by default."),
+            (
+                "wide",
+                "Switch between the warehouses of part one and part two.",
+            ),
+            ("reset", "Go back to the start."),
+        ]
+    }
 
-        let mut count = 0;
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &cell) in row.iter().enumerate() {
-                if cell == by {
-                    count += y * 100 + x
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "show" => {}
+            "step" => {
+                let n = match args.first() {
+                    Some(n) => n.parse().map_err(|_| format!("`{n}` is not a number"))?,
+                    None => 1,
+                };
+                let attempt = if self.wide {
+                    Warehouse::attempt2
+                } else {
+                    Warehouse::attempt
+                };
+                for motion in self
+                    .warehouse
+                    .motions
+                    .clone()
+                    .into_iter()
+                    .skip(self.steps)
+                    .take(n)
+                {
+                    attempt(&mut self.warehouse, motion);
+                    self.steps += 1;
                 }
             }
+            "wide" => {
+                self.wide = !self.wide;
+                self.reset();
+            }
+            "reset" => self.reset(),
+            _ => return Err(format!("unknown command `{command}`")),
         }
-
-        Some(count as u64)
+        Ok(self.show())
     }
 }
 
@@ -272,4 +315,15 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(9021));
     }
+
+    #[test]
+    fn test_inspector() {
+        let mut inspector = Inspector::new(&advent_of_code::template::read_file("examples", DAY));
+        let shown = inspector.run("step", &["700"]).unwrap();
+        assert!(shown.ends_with("after 700 of 700 moves (part 1), GPS sum 10092"));
+
+        let shown = inspector.run("wide", &[]).unwrap();
+        assert!(shown.starts_with("####################\n##....[]....[]..[]##"));
+        assert!(inspector.run("step", &["many"]).is_err());
+    }
 }
@@ -1,114 +1,149 @@
-use std::collections::{BinaryHeap, HashSet};
+use std::collections::HashMap;
 
-advent_of_code::solution!(16);
+use advent_of_code::graph::{self, ShortestPaths};
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::template::inspect::Inspect;
+use advent_of_code::viz;
+
+advent_of_code::solution!(16; inspect: Maze);
+
+/// A reindeer's position and the direction it is facing.
+type State = (Point, Point);
 
-#[allow(dead_code)]
 struct Maze {
-    tiles: Vec<Vec<char>>,
-    start: Position,
-    end: Position,
+    tiles: Grid<char>,
+    start: Point,
+    end: Point,
 }
 
-#[allow(dead_code)]
 impl Maze {
     fn new(input: &str) -> Self {
-        let mut tiles = Vec::new();
-        let mut start = Position::new(0, 0);
-        let mut end = Position::new(0, 0);
-
-        for (y, line) in input.lines().enumerate() {
-            let mut row = Vec::new();
-            for (x, ch) in line.chars().enumerate() {
-                if ch == 'S' {
-                    start = Position::new(x, y);
-                } else if ch == 'E' {
-                    end = Position::new(x, y);
-                }
-                row.push(ch);
-            }
-            tiles.push(row);
-        }
+        let tiles = Grid::parse(input);
+        let start = tiles
+            .position_of(&'S')
+            .expect("the maze should have a start");
+        let end = tiles
+            .position_of(&'E')
+            .expect("the maze should have an end");
 
         Maze { tiles, start, end }
     }
 
-    fn get(&self, pos: Position) -> char {
-        self.tiles[pos.y][pos.x]
+    fn moves(&self, &(pos, dir): &State) -> Vec<(State, usize)> {
+        let mut moves = vec![
+            ((pos, dir.turn_left()), 1000),
+            ((pos, dir.turn_right()), 1000),
+        ];
+        if self.tiles.get(pos + dir).is_some_and(|tile| *tile != '#') {
+            moves.push(((pos + dir, dir), 1));
+        }
+        moves
     }
 
-    fn solve(&mut self) -> (usize, usize) {
-        use Direction::*;
-
-        let start_tile = self.start;
-        let end_tile = self.end;
-        let mut min = BinaryHeap::new();
-        let mut visited = HashSet::new();
-        let mut min_cost = usize::MAX;
-        let mut neighbor_walls = HashSet::new();
-
-        [Right, Up, Down, Left].iter().for_each(|&dir| {
-            min.push(State {
-                cost: 0,
-                position: start_tile,
-                direction: dir,
-                walls_around: HashSet::new(),
-            })
-        });
-
-        while let Some(State {
-            cost: cc,
-            position: cp,
-            direction: cd,
-            walls_around: mut wa,
-        }) = min.pop()
-        {
-            wa.insert(cp);
-
-            if cp == end_tile {
-                if cc > min_cost {
-                    break;
-                } else {
-                    min_cost = cc;
-                    neighbor_walls = neighbor_walls.union(&wa).cloned().collect();
-                    continue;
-                }
-            }
+    /// The lowest scores of all states the reindeer can reach.
+    fn search(&self) -> ShortestPaths<State, usize> {
+        // NOTE: the reindeer may start off facing any direction.
+        let starts = Point::ORTHOGONAL.map(|dir| (self.start, dir));
+        graph::shortest_paths(starts, |state| self.moves(state))
+    }
 
-            visited.insert((cp, cd));
+    /// Finds the lowest score and the tiles that are part of a path with that score.
+    fn best_paths(&self) -> Option<(usize, Vec<Point>)> {
+        let paths = self.search();
+
+        let ends = Point::ORTHOGONAL.map(|dir| (self.end, dir));
+        let min_cost = ends.iter().filter_map(|end| paths.cost(end)).min()?;
+
+        let best_ends = ends
+            .into_iter()
+            .filter(|end| paths.cost(end) == Some(min_cost));
+        let mut tiles: Vec<Point> = paths
+            .nodes_on_paths_to(best_ends)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect();
+        tiles.sort_unstable();
+        tiles.dedup();
+
+        Some((min_cost, tiles))
+    }
 
-            let np = cp.add(cd);
-            if self.get(np) != '#' && !visited.contains(&(np, cd)) {
-                min.push(State {
-                    cost: cc + 1,
-                    position: np,
-                    direction: cd,
-                    walls_around: wa.clone(),
-                });
+    /// Shows the search reaching the tiles in the order of their lowest score, marked as `X`,
+    /// then the tiles of the best paths as `O`, for `--viz`.
+    fn animate(&self) {
+        let mut scores: HashMap<Point, usize> = HashMap::new();
+        for (&(pos, _), &cost) in self.search().costs() {
+            let score = scores.entry(pos).or_insert(cost);
+            *score = (*score).min(cost);
+        }
+        let mut order: Vec<(usize, Point)> = scores
+            .into_iter()
+            .map(|(pos, score)| (score, pos))
+            .collect();
+        order.sort_unstable();
+
+        let mut maze = self.tiles.clone();
+        viz::push_chars(&maze);
+        for (_, pos) in order {
+            if maze[pos] == '.' {
+                maze[pos] = 'X';
+                viz::push_chars(&maze);
             }
+        }
+        let (_, best) = self.best_paths().unwrap_or_default();
+        for tile in best {
+            maze[tile] = 'O';
+        }
+        viz::push_chars(&maze);
+    }
 
-            for nd in cd.turns() {
-                if !visited.contains(&(cp, nd)) && self.get(cp.add(nd)) != '#' {
-                    min.push(State {
-                        cost: cc + 1001,
-                        position: cp.add(nd),
-                        direction: nd,
-                        walls_around: wa.clone(),
-                    });
-                }
-            }
+    fn solve(&self) -> (usize, usize) {
+        self.best_paths()
+            .map_or((usize::MAX, 0), |(cost, tiles)| (cost, tiles.len()))
+    }
+}
+
+impl Inspect for Maze {
+    fn new(input: &str) -> Self {
+        Maze::new(input)
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[(
+            "show",
+            "Print the maze with the tiles of the best paths marked as `O`.",
+        )]
+    }
+
+    fn run(&mut self, command: &str, _args: &[&str]) -> Result<String, String> {
+        if command != "show" {
+            return Err(format!("unknown command `{command}`"));
         }
+        let Some((cost, tiles)) = self.best_paths() else {
+            return Err("the end can't be reached".into());
+        };
 
-        (min_cost, neighbor_walls.len())
+        let mut maze = self.tiles.clone();
+        for tile in &tiles {
+            maze[*tile] = 'O';
+        }
+        Ok(format!(
+            "{maze}\nscore {cost}, {} tiles on the best paths",
+            tiles.len()
+        ))
     }
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut maze = Maze::new(input);
+    let maze = Maze::new(input);
+    if viz::enabled() {
+        maze.animate();
+    }
     maze.solve().0.into()
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut maze = Maze::new(input);
+    let maze = Maze::new(input);
     maze.solve().1.into()
 }
 
@@ -128,102 +163,3 @@ mod tests {
         assert_eq!(result, Some(45));
     }
 }
-
-#[allow(dead_code)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[allow(dead_code)]
-impl Direction {
-    fn turns(self) -> [Self; 2] {
-        use Direction::*;
-        match self {
-            Down | Up => [Right, Left],
-            Left | Right => [Up, Down],
-        }
-    }
-}
-
-#[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-#[allow(dead_code)]
-impl Position {
-    fn new(x: usize, y: usize) -> Self {
-        Position { x, y }
-    }
-
-    fn add(&self, dir: Direction) -> Position {
-        use Direction::*;
-        match dir {
-            Up => Position::new(self.x, self.y - 1),
-            Down => Position::new(self.x, self.y + 1),
-            Left => Position::new(self.x - 1, self.y),
-            Right => Position::new(self.x + 1, self.y),
-        }
-    }
-}
-
-#[allow(dead_code)]
-#[derive(Clone, Debug)]
-struct State {
-    cost: usize,
-    position: Position,
-    direction: Direction,
-    walls_around: HashSet<Position>,
-}
-
-impl PartialEq for State {
-    fn eq(&self, other: &Self) -> bool {
-        self.cost.eq(&other.cost)
-    }
-}
-
-impl Eq for State {}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-#[allow(dead_code)]
-fn print(maze: &Maze, cc: usize, cp: Position, cd: Direction) {
-    println!("Cost: {}", cc);
-    println!("Position: ({}, {})", cp.x, cp.y);
-    println!("Direction: {:?}", cd);
-    for y in 0..maze.tiles.len() {
-        for x in 0..maze.tiles[0].len() {
-            match maze.get(Position::new(x, y)) {
-                '#' => print!("#"),
-                '.' => {
-                    if cp.x == x && cp.y == y {
-                        print!("X");
-                    } else {
-                        print!(".");
-                    }
-                }
-                'S' => print!("S"),
-                'E' => print!("E"),
-                _ => print!(" "),
-            }
-        }
-        println!()
-    }
-    println!()
-}
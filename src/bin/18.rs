@@ -1,127 +1,89 @@
-use std::collections::VecDeque;
+use advent_of_code::graph;
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::viz;
 
 advent_of_code::solution!(18);
 
-const DIRECTIONS: [(i64, i64); 4] = [
-    (0, 1),  // Down
-    (1, 0),  // Right
-    (0, -1), // Up
-    (-1, 0), // Left
-];
-
-#[allow(dead_code)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Byte {
-    Free,
-    Bomb,
-    Visited(i64),
-}
-
-impl Byte {
-    fn value(&self) -> i64 {
-        match self {
-            Byte::Free => -1,
-            Byte::Bomb => -1,
-            Byte::Visited(v) => *v,
-        }
-    }
-}
-
-#[allow(dead_code)]
 struct Memory {
-    bytes: i64,
-    size: i64,
-    bombs: Vec<(usize, usize)>,
+    bytes: usize,
+    size: usize,
+    bombs: Vec<Point>,
 }
 
-#[allow(dead_code)]
 impl Memory {
-    fn new(input: &str, bytes: i64, size: i64) -> Self {
-        let grid = input
+    fn new(input: &str, bytes: usize, size: usize) -> Self {
+        let bombs = input
             .lines()
             .map(|line| {
                 let (x, y) = line.split_once(',').unwrap();
-                (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap())
+                Point::new(x.parse().unwrap(), y.parse().unwrap())
             })
-            .collect::<Vec<(usize, usize)>>();
+            .collect();
 
-        Self {
-            bombs: grid,
-            bytes,
-            size,
-        }
+        Self { bytes, size, bombs }
     }
 
-    fn shortest_path(&self) -> Option<i64> {
-        use Byte::*;
-        let size = self.size as usize;
-        let mut grid: Vec<Vec<Byte>> = vec![vec![Free; size]; size];
-        let mut queue: VecDeque<(i64, i64)> = VecDeque::from([(0, 0)]);
-        let mut level_count: i64 = 0;
-
-        grid[0][0] = Visited(0);
-        for &(x, y) in self.bombs.iter().take(self.bytes as usize) {
-            grid[y][x] = Bomb;
-        }
+    /// The fewest steps from the top-left to the bottom-right corner after `fallen` bytes fell.
+    fn shortest_path(&self, fallen: usize) -> Option<usize> {
+        self.path(fallen).map(|path| path.len() - 1)
+    }
 
-        while !queue.is_empty() {
-            let mut new_level = VecDeque::new();
-
-            for (x, y) in queue.drain(..) {
-                if x == self.size - 1 && y == self.size - 1 {
-                    return Some(level_count);
-                }
-
-                for &(dx, dy) in &DIRECTIONS {
-                    let (nx, ny) = (x + dx, y + dy);
-                    if nx >= 0
-                        && nx < self.size
-                        && ny >= 0
-                        && ny < self.size
-                        && grid[ny as usize][nx as usize] == Free
-                    {
-                        grid[ny as usize][nx as usize] = Visited(level_count + 1);
-                        new_level.push_back((nx, ny));
-                    }
-                }
-            }
-
-            level_count += 1;
-            queue = new_level;
+    /// A shortest path from the top-left to the bottom-right corner after `fallen` bytes fell.
+    fn path(&self, fallen: usize) -> Option<Vec<Point>> {
+        let mut grid = Grid::new(self.size, self.size, false);
+        for bomb in &self.bombs[..fallen] {
+            grid[*bomb] = true;
         }
 
-        Some(grid[size - 1][size - 1].value())
+        let exit = Point::new(self.size as i32 - 1, self.size as i32 - 1);
+        graph::bfs(
+            [Point::ORIGIN],
+            |pos| {
+                grid.neighbours4(*pos)
+                    .filter(|next| !grid[*next])
+                    .collect::<Vec<_>>()
+            },
+            |pos| *pos == exit,
+        )
     }
 
-    fn first_blocker(&mut self) -> Option<String> {
-        loop {
-            let path_len = self.shortest_path();
-            if path_len.is_none() {
-                continue;
-            }
-            let path_len = path_len.unwrap();
-
-            match path_len {
-                -1 => return self.str_from_coord(self.bytes as usize - 1).into(),
-                _ => self.bytes += 1,
-            }
+    /// Shows the bytes falling one by one, then the shortest path around them, for `--viz`.
+    fn animate(&self, fallen: usize) {
+        let mut grid = Grid::new(self.size, self.size, '.');
+        viz::push_chars(&grid);
+        for bomb in &self.bombs[..fallen] {
+            grid[*bomb] = '#';
+            viz::push_chars(&grid);
         }
+        for pos in self.path(fallen).unwrap_or_default() {
+            grid[pos] = 'O';
+        }
+        viz::push_chars(&grid);
     }
 
-    #[inline]
-    fn str_from_coord(&self, idx: usize) -> String {
-        let coord = self.bombs[idx];
-        format!("{},{}", coord.0, coord.1)
+    fn first_blocker(&self) -> Option<String> {
+        // NOTE: once the exit is cut off it stays cut off, so the first blocker can be bisected.
+        let counts: Vec<usize> = (self.bytes..=self.bombs.len()).collect();
+        let fallen = counts.get(counts.partition_point(|n| self.shortest_path(*n).is_some()))?;
+        let blocker = self.bombs[fallen - 1];
+        Some(format!("{},{}", blocker.x, blocker.y))
     }
 }
 
+/// Part one lets the first kilobyte fall into the 71×71 memory space.
+const BYTES: usize = 1024;
+const SIZE: usize = 71;
+
 pub fn part_one(input: &str) -> Option<i64> {
-    let memory = Memory::new(input, 1024, 71);
-    memory.shortest_path()
+    let memory = Memory::new(input, BYTES, SIZE);
+    if viz::enabled() {
+        memory.animate(BYTES);
+    }
+    memory.shortest_path(BYTES).map(|steps| steps as i64)
 }
 
 pub fn part_two(input: &str) -> Option<String> {
-    let mut memory = Memory::new(input, 1024, 71);
+    let memory = Memory::new(input, BYTES, SIZE);
     memory.first_blocker()
 }
 
@@ -129,15 +91,20 @@ pub fn part_two(input: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    /// The example's memory space is 7×7 and part one lets its first 12 bytes fall.
+    fn example() -> Memory {
+        Memory::new(&advent_of_code::template::read_file("examples", DAY), 12, 7)
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(22));
+        let memory = example();
+        assert_eq!(memory.shortest_path(12).map(|steps| steps as i64), Some(22));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("6,1".to_string()));
+        let memory = example();
+        assert_eq!(memory.first_blocker(), Some("6,1".to_string()));
     }
 }
@@ -1,64 +1,35 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-advent_of_code::solution!(20);
+use advent_of_code::grid::{Grid, Point};
 
-type Pos = (i64, i64);
-type Grid = Vec<Vec<char>>;
-const OFFSETS: [(i64, i64); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+advent_of_code::solution!(20);
 
-fn parse_input(lines: &[String]) -> (Grid, Pos, Pos) {
-    let mut grid: Grid = vec![];
-    let mut start_pos = (0, 0);
-    let mut end_pos = (0, 0);
-    for (y, line) in lines.iter().enumerate() {
-        grid.push(vec![]);
-        for (x, c) in line.chars().enumerate() {
-            let pos = (x as i64, y as i64);
-            match c {
-                'S' => start_pos = pos,
-                'E' => end_pos = pos,
-                _ => {}
-            }
-            grid[y].push(c);
-        }
-    }
-    let default_grid = grid.clone();
-    (default_grid, start_pos, end_pos)
+fn parse_input(input: &str) -> (Grid<char>, Point, Point) {
+    let grid = Grid::parse(input);
+    let start_pos = grid
+        .position_of(&'S')
+        .expect("the track should have a start");
+    let end_pos = grid
+        .position_of(&'E')
+        .expect("the track should have an end");
+    (grid, start_pos, end_pos)
 }
 
-fn find_path(grid: &Grid, start: Pos, end: Pos) -> HashMap<Pos, i64> {
-    let mut path: HashMap<Pos, i64> = HashMap::new();
+fn find_path(grid: &Grid<char>, start: Point, end: Point) -> HashMap<Point, i32> {
+    let mut path: HashMap<Point, i32> = HashMap::new();
     let mut pos = start;
-    let mut visited = HashSet::new();
     path.insert(start, 0);
-    visited.insert(start);
     while pos != end {
-        let mut found = false;
-        for &offset in &OFFSETS {
-            let next = ((pos.0 + offset.0), pos.1 + offset.1);
-            if next.0 < 0
-                || next.1 < 0
-                || next.1 as usize >= grid.len()
-                || next.0 as usize >= grid[0].len()
-                || grid[next.1 as usize][next.0 as usize] == '#'
-                || visited.contains(&next)
-            {
-                continue;
-            }
-            pos = next;
-            visited.insert(pos);
-            path.insert(pos, path.len() as i64);
-            found = true;
-            break;
-        }
-        if !found {
-            panic!("No path found");
-        }
+        pos = grid
+            .neighbours4(pos)
+            .find(|next| grid[*next] != '#' && !path.contains_key(next))
+            .expect("No path found");
+        path.insert(pos, path.len() as i32);
     }
     path
 }
 
-fn count_good_cheats(grid: &Grid, start: Pos, end: Pos, cheat_length: i64) -> i64 {
+fn count_good_cheats(grid: &Grid<char>, start: Point, end: Point, cheat_length: i32) -> i64 {
     let path = find_path(grid, start, end);
     let mut good_cheats = 0;
     for (&pos, &move_num) in &path {
@@ -66,19 +37,10 @@ fn count_good_cheats(grid: &Grid, start: Pos, end: Pos, cheat_length: i64) -> i6
         for dx in -r..=r {
             let dy_limit = r - dx.abs();
             for dy in -dy_limit..=dy_limit {
-                let cheat_pos = (pos.0 + dx, pos.1 + dy);
-                if let Some(&c) = grid
-                    .get(cheat_pos.1 as usize)
-                    .and_then(|row| row.get(cheat_pos.0 as usize))
-                {
-                    if c == '#' {
-                        continue;
-                    }
-                } else {
-                    continue; // Outside grid
-                }
+                let cheat_pos = pos + Point::new(dx, dy);
+                // NOTE: walls and positions outside of the grid are not on the path.
                 if let Some(&cheat_move) = path.get(&cheat_pos) {
-                    let dist = dx.abs() + dy.abs();
+                    let dist = pos.manhattan(cheat_pos);
                     if cheat_move > move_num + dist && (cheat_move - (move_num + dist)) >= 100 {
                         good_cheats += 1;
                     }
@@ -90,15 +52,13 @@ fn count_good_cheats(grid: &Grid, start: Pos, end: Pos, cheat_length: i64) -> i6
 }
 
 pub fn part_one(_input: &str) -> Option<i64> {
-    let lines: Vec<String> = _input.lines().map(|line| line.to_string()).collect();
-    let (grid, start_pos, end_pos) = parse_input(&lines);
+    let (grid, start_pos, end_pos) = parse_input(_input);
     let result = count_good_cheats(&grid, start_pos, end_pos, 2);
     Some(result)
 }
 
 pub fn part_two(_input: &str) -> Option<i64> {
-    let lines: Vec<String> = _input.lines().map(|line| line.to_string()).collect();
-    let (grid, start_pos, end_pos) = parse_input(&lines);
+    let (grid, start_pos, end_pos) = parse_input(_input);
     let result = count_good_cheats(&grid, start_pos, end_pos, 20);
     Some(result)
 }
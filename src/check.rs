@@ -0,0 +1,440 @@
+//! Differential testing: runs a solution and a simple reference implementation over generated
+//! inputs and shrinks any input they disagree on.
+//!
+//! A day registers the check in its tests, next to a brute-force version of a part:
+//!
+//! ```no_run
+//! use advent_of_code::check::{self, Rng};
+//! # fn part_one(input: &str) -> Option<u64> { None }
+//! # fn part_one_naive(input: &str) -> Option<u64> { None }
+//!
+//! /// Generates an input of roughly `size` elements.
+//! fn generate(rng: &mut Rng, size: usize) -> String {
+//!     (0..size).map(|_| rng.between(0, 9).to_string()).collect()
+//! }
+//!
+//! check::differential(generate, part_one, part_one_naive).run(advent_of_code::day!(9), "part_one");
+//! ```
+//!
+//! A disagreement fails the test with the smallest input found, which is also saved to
+//! `data/counterexamples/<day>-<name>.txt` so it can be turned into a regular example test.
+//! Inputs on which the reference panics are considered invalid and skipped. Set
+//! `AOC_CHECK_SEED` to try other inputs than the default, deterministic ones.
+use std::{
+    any::Any,
+    cell::Cell,
+    env,
+    fmt::{Debug, Display},
+    fs,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+use crate::template::{config, Day};
+
+/// Seed used unless `AOC_CHECK_SEED` is set.
+const DEFAULT_SEED: u64 = 0x5eed_2024;
+/// Upper bound on the inputs tried while shrinking a counterexample.
+const MAX_SHRINK_ATTEMPTS: usize = 10_000;
+
+/// A small, deterministic pseudo-random number generator (SplitMix64) for input generators.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `low..=high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "empty range {low}..={high}");
+        let span = (high - low).wrapping_add(1);
+        if span == 0 {
+            return self.next_u64();
+        }
+        low + ((u128::from(self.next_u64()) * u128::from(span)) >> 64) as u64
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    /// Returns a random element of `items`.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.between(0, items.len() as u64 - 1) as usize]
+    }
+
+    /// Shuffles `items` in place.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.between(0, i as u64) as usize);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An input on which the implementation and the reference disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample {
+    pub seed: u64,
+    pub case: usize,
+    /// The shrunk input.
+    pub input: String,
+    /// The reference's result, formatted with `{:?}`.
+    pub expected: String,
+    /// The implementation's result formatted with `{:?}`, or its panic message.
+    pub actual: String,
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "implementation and reference disagree (seed {}, case {}) on input:",
+            self.seed, self.case
+        )?;
+        writeln!(f, "{}", self.input)?;
+        writeln!(f, "expected: {}", self.expected)?;
+        write!(f, "actual:   {}", self.actual)
+    }
+}
+
+/// Compares `implementation` with `reference` on inputs from `generate`, see the [module docs](self).
+///
+/// `generate` receives the size of the input to create, which grows with each case.
+pub fn differential<G, I, R, T>(
+    generate: G,
+    implementation: I,
+    reference: R,
+) -> Differential<G, I, R, T>
+where
+    G: Fn(&mut Rng, usize) -> String,
+    I: Fn(&str) -> T,
+    R: Fn(&str) -> T,
+    T: PartialEq + Debug,
+{
+    let seed = env::var("AOC_CHECK_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(DEFAULT_SEED);
+
+    Differential {
+        generate,
+        implementation,
+        reference,
+        cases: 100,
+        max_size: 20,
+        seed,
+        result: PhantomData,
+    }
+}
+
+/// A configured differential check, created by [`differential`].
+pub struct Differential<G, I, R, T> {
+    generate: G,
+    implementation: I,
+    reference: R,
+    cases: usize,
+    max_size: usize,
+    seed: u64,
+    result: PhantomData<fn() -> T>,
+}
+
+impl<G, I, R, T> Differential<G, I, R, T>
+where
+    G: Fn(&mut Rng, usize) -> String,
+    I: Fn(&str) -> T,
+    R: Fn(&str) -> T,
+    T: PartialEq + Debug,
+{
+    /// Sets the number of inputs to try. Defaults to 100.
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Sets the size passed to the generator for the last case. Defaults to 20.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the seed of the generated inputs, overriding `AOC_CHECK_SEED`.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Runs the check, saving the counterexample and panicking if one is found.
+    pub fn run(&self, day: Day, name: &str) {
+        let Some(counterexample) = self.find_counterexample() else {
+            return;
+        };
+
+        let dir = config::get().paths.folder("counterexamples");
+        let path = dir.join(format!("{day}-{name}.txt"));
+        let saved = fs::create_dir_all(&dir).and_then(|_| fs::write(&path, &counterexample.input));
+
+        match saved {
+            Ok(()) => panic!(
+                "{counterexample}\nsaved the input to \"{}\"",
+                path.display()
+            ),
+            Err(e) => panic!("{counterexample}\ncould not save the input: {e}"),
+        }
+    }
+
+    /// Runs the check, returning the shrunk counterexample if one is found.
+    pub fn find_counterexample(&self) -> Option<Counterexample> {
+        for case in 0..self.cases {
+            let mut rng = Rng::new(self.seed ^ (case as u64).wrapping_mul(0x2545_f491_4f6c_dd1d));
+            let size =
+                1 + case * self.max_size.saturating_sub(1) / self.cases.max(2).saturating_sub(1);
+            let input = (self.generate)(&mut rng, size);
+
+            if self.compare(&input).is_some() {
+                let input = shrink(input, |candidate| self.compare(candidate).is_some());
+                let (expected, actual) = self.compare(&input).unwrap();
+                return Some(Counterexample {
+                    seed: self.seed,
+                    case,
+                    input,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Returns both results formatted if they differ and the reference doesn't panic.
+    fn compare(&self, input: &str) -> Option<(String, String)> {
+        let expected = quietly(|| (self.reference)(input)).ok()?;
+        match quietly(|| (self.implementation)(input)) {
+            Ok(actual) if actual == expected => None,
+            Ok(actual) => Some((format!("{expected:?}"), format!("{actual:?}"))),
+            Err(message) => Some((format!("{expected:?}"), format!("panicked: {message}"))),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reduces `input` while it still `fails`: drops lines, then characters, then lowers digits.
+fn shrink(mut input: String, fails: impl Fn(&str) -> bool) -> String {
+    let mut attempts = 0;
+
+    'improve: loop {
+        for candidate in candidates(&input) {
+            attempts += 1;
+            if attempts > MAX_SHRINK_ATTEMPTS {
+                break 'improve;
+            }
+            if fails(&candidate) {
+                input = candidate;
+                continue 'improve;
+            }
+        }
+        break;
+    }
+
+    input
+}
+
+/// Smaller variants of `input`, roughly ordered by how much they remove.
+fn candidates(input: &str) -> Vec<String> {
+    let mut candidates = vec![];
+
+    let lines: Vec<&str> = input.lines().collect();
+    if lines.len() > 1 {
+        candidates.extend(
+            without_chunks(lines.len())
+                .map(|(start, end)| [&lines[..start], &lines[end..]].concat().join("\n")),
+        );
+    }
+
+    let chars: Vec<char> = input.chars().collect();
+    candidates.extend(
+        without_chunks(chars.len())
+            .map(|(start, end)| chars[..start].iter().chain(&chars[end..]).collect()),
+    );
+
+    for (i, c) in chars.iter().enumerate() {
+        let Some(digit) = c.to_digit(10) else {
+            continue;
+        };
+        for smaller in [0, digit / 2, digit.saturating_sub(1)] {
+            if smaller < digit {
+                let mut candidate = chars.clone();
+                candidate[i] = char::from_digit(smaller, 10).unwrap();
+                candidates.push(candidate.into_iter().collect());
+            }
+        }
+    }
+
+    candidates.dedup();
+    candidates
+}
+
+/// Ranges to remove from a sequence of `len` items, halving the chunk size down to one.
+fn without_chunks(len: usize) -> impl Iterator<Item = (usize, usize)> {
+    std::iter::successors((len > 0).then_some(len.div_ceil(2)), |size| {
+        (*size > 1).then(|| size / 2)
+    })
+    .flat_map(move |size| {
+        (0..len)
+            .step_by(size)
+            .map(move |start| (start, (start + size).min(len)))
+    })
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Calls `f`, catching a panic without printing it and returning its message instead.
+pub(crate) fn quietly<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                default(info);
+            }
+        }));
+    });
+
+    QUIET.with(|quiet| quiet.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.with(|quiet| quiet.set(false));
+
+    result.map_err(|payload| panic_message(payload.as_ref()))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{differential, shrink, Rng};
+
+    fn digits(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| rng.between(0, 9).to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn digit_sum(input: &str) -> u32 {
+        input.chars().filter_map(|c| c.to_digit(10)).sum()
+    }
+
+    #[test]
+    fn generates_deterministically() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert_eq!(digits(&mut a, 20), digits(&mut b, 20));
+
+        let mut rng = Rng::new(1);
+        assert!((0..1000)
+            .map(|_| rng.between(3, 5))
+            .all(|n| (3..=5).contains(&n)));
+        assert!((0..1000).any(|_| rng.between(3, 5) == 5));
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn passes_equal_implementations() {
+        let check = differential(digits, digit_sum, |input: &str| {
+            input.lines().map(|line| line.parse::<u32>().unwrap()).sum()
+        });
+        assert_eq!(check.find_counterexample(), None);
+    }
+
+    #[test]
+    fn shrinks_counterexamples() {
+        // NOTE: the "optimized" version forgets about sevens.
+        let buggy = |input: &str| {
+            input
+                .chars()
+                .filter_map(|c| c.to_digit(10))
+                .filter(|d| *d != 7)
+                .sum::<u32>()
+        };
+        let counterexample = differential(digits, buggy, digit_sum)
+            .max_size(50)
+            .find_counterexample()
+            .unwrap();
+
+        assert_eq!(counterexample.input, "7");
+        assert_eq!(
+            (
+                counterexample.expected.as_str(),
+                counterexample.actual.as_str()
+            ),
+            ("7", "0")
+        );
+    }
+
+    #[test]
+    fn reports_panics_and_skips_invalid_inputs() {
+        let strict = |input: &str| {
+            input
+                .lines()
+                .map(|line| line.parse::<u32>().unwrap())
+                .sum::<u32>()
+        };
+        let picky = |input: &str| {
+            assert!(!input.contains('9'), "nines are not supported");
+            strict(input)
+        };
+
+        let counterexample = differential(digits, picky, strict)
+            .find_counterexample()
+            .unwrap();
+        assert_eq!(counterexample.input, "9");
+        assert_eq!(counterexample.actual, "panicked: nines are not supported");
+
+        // NOTE: the reference rejecting an input makes it invalid, not a counterexample.
+        assert_eq!(
+            differential(digits, strict, picky).find_counterexample(),
+            None
+        );
+    }
+
+    #[test]
+    fn shrinks_lines_and_digits() {
+        let shrunk = shrink("12\n48\n93".into(), |input| {
+            input
+                .lines()
+                .any(|line| line.parse::<u32>().is_ok_and(|n| n >= 40))
+        });
+        assert_eq!(shrunk, "40");
+    }
+}
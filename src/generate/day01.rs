@@ -0,0 +1,27 @@
+//! Two columns of location IDs. About half of the right column repeats IDs from the left one,
+//! so the similarity score of part two is not zero.
+use super::between;
+use crate::check::Rng;
+
+pub const UNIT: &str = "pairs";
+pub const DEFAULT_SIZE: usize = 1000;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let left: Vec<usize> = (0..size).map(|_| between(rng, 10000, 99999)).collect();
+    let right: Vec<usize> = (0..size)
+        .map(|_| {
+            if rng.chance(0.5) {
+                *rng.choose(&left)
+            } else {
+                between(rng, 10000, 99999)
+            }
+        })
+        .collect();
+
+    left.iter()
+        .zip(&right)
+        .map(|(l, r)| format!("{l}   {r}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,33 @@
+//! Reports of five to eight levels. Most are steadily increasing or decreasing, some have one
+//! level out of place so the problem dampener of part two matters.
+use super::{between, signed};
+use crate::check::Rng;
+
+pub const UNIT: &str = "reports";
+pub const DEFAULT_SIZE: usize = 1000;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let len = between(rng, 5, 8);
+            let direction = if rng.chance(0.5) { 1 } else { -1 };
+            let mut level = signed(rng, 1, 99);
+            let mut levels = vec![level];
+            for _ in 1..len {
+                level = (level + direction * signed(rng, 1, 3)).clamp(1, 99);
+                levels.push(level);
+            }
+            if rng.chance(0.5) {
+                let i = between(rng, 0, len - 1);
+                levels[i] = (levels[i] + signed(rng, -4, 4)).clamp(1, 99);
+            }
+            levels
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,79 @@
+//! Corrupted memory: `mul(a,b)`, `do()` and `don't()` instructions between garbage that includes
+//! near misses like `mul(4*` or `mul ( 2 , 4 )`.
+use super::between;
+use crate::check::Rng;
+
+pub const UNIT: &str = "instructions";
+pub const DEFAULT_SIZE: usize = 700;
+pub const MIN_SIZE: usize = 1;
+
+const GARBAGE: &[&str] = &[
+    "!",
+    "@",
+    "#",
+    "$",
+    "%",
+    "^",
+    "&",
+    "*",
+    "(",
+    ")",
+    "[",
+    "]",
+    "{",
+    "}",
+    "<",
+    ">",
+    "'",
+    "+",
+    "-",
+    "?",
+    "/",
+    ",",
+    ";",
+    ":",
+    " ",
+    "~",
+    "from()",
+    "when()",
+    "what()",
+    "select()",
+    "how()",
+    "why()",
+    "who()",
+    "where()",
+    "mul(4*",
+    "mul ( 2 , 4 )",
+    "mul[3,7]",
+    "do_not_mul(5,5)",
+    "don't",
+    "mul(32,64]",
+    "mul(1234,5)",
+];
+
+/// Instructions per line of the input.
+const PER_LINE: usize = 120;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut lines = vec![String::new()];
+    for i in 0..size {
+        if i > 0 && i % PER_LINE == 0 {
+            lines.push(String::new());
+        }
+        let line = lines.last_mut().unwrap();
+        for _ in 0..between(rng, 0, 6) {
+            let garbage = *rng.choose(GARBAGE);
+            line.push_str(garbage);
+        }
+        match between(rng, 0, 9) {
+            0 => line.push_str("do()"),
+            1 => line.push_str("don't()"),
+            _ => line.push_str(&format!(
+                "mul({},{})",
+                between(rng, 1, 999),
+                between(rng, 1, 999)
+            )),
+        }
+    }
+    lines.join("\n")
+}
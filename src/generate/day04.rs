@@ -0,0 +1,17 @@
+//! A word search of the letters `X`, `M`, `A` and `S`.
+use crate::check::Rng;
+
+pub const UNIT: &str = "rows and columns";
+pub const DEFAULT_SIZE: usize = 140;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| *rng.choose(&['X', 'M', 'A', 'S']))
+                .collect()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,41 @@
+//! Page ordering rules for every pair of 49 pages, followed by updates of an odd number of pages.
+//! About half of the updates are in the right order.
+use super::between;
+use crate::check::Rng;
+
+pub const UNIT: &str = "updates";
+pub const DEFAULT_SIZE: usize = 200;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut order: Vec<usize> = (10..=99).collect();
+    rng.shuffle(&mut order);
+    order.truncate(49);
+
+    let mut rules = vec![];
+    for (i, before) in order.iter().enumerate() {
+        for after in &order[i + 1..] {
+            rules.push(format!("{before}|{after}"));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let updates: Vec<String> = (0..size)
+        .map(|_| {
+            let len = between(rng, 2, 11) * 2 + 1;
+            let mut positions: Vec<usize> = (0..order.len()).collect();
+            rng.shuffle(&mut positions);
+            positions.truncate(len);
+            if rng.chance(0.5) {
+                positions.sort_unstable();
+            }
+            positions
+                .iter()
+                .map(|&i| order[i].to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect();
+
+    format!("{}\n\n{}", rules.join("\n"), updates.join("\n"))
+}
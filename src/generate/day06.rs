@@ -0,0 +1,40 @@
+//! A lab map with scattered obstructions and the guard `^`. Maps on which the guard would walk
+//! in a loop are discarded, the guard always leaves the area in part one.
+use std::collections::HashSet;
+
+use super::between;
+use crate::check::Rng;
+use crate::grid::{Grid, Point};
+
+pub const UNIT: &str = "rows and columns";
+pub const DEFAULT_SIZE: usize = 130;
+pub const MIN_SIZE: usize = 2;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let mut grid = Grid::from_fn(size, size, |_| if rng.chance(0.045) { '#' } else { '.' });
+        let guard = Point::new(
+            between(rng, 0, size - 1) as i32,
+            between(rng, 0, size - 1) as i32,
+        );
+        grid[guard] = '.';
+        if leaves(&grid, guard) {
+            grid[guard] = '^';
+            return grid.to_string();
+        }
+    }
+}
+
+/// Whether the guard walks off the map when starting at `guard` facing up.
+fn leaves(grid: &Grid<char>, guard: Point) -> bool {
+    let (mut pos, mut dir) = (guard, Point::UP);
+    let mut seen = HashSet::new();
+    while seen.insert((pos, dir)) {
+        match grid.get(pos + dir) {
+            Some('#') => dir = dir.turn_right(),
+            Some(_) => pos += dir,
+            None => return true,
+        }
+    }
+    false
+}
@@ -0,0 +1,43 @@
+//! Calibration equations of two to twelve numbers. About half of them can be made true with
+//! `+`, `*` or `||`, the others are off by a little.
+use super::between;
+use crate::check::Rng;
+
+pub const UNIT: &str = "equations";
+pub const DEFAULT_SIZE: usize = 850;
+pub const MIN_SIZE: usize = 1;
+
+/// Test values stay well below `u64::MAX`.
+const LIMIT: u64 = 1_000_000_000_000_000;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut equations = vec![];
+    while equations.len() < size {
+        let len = between(rng, 2, 12);
+        let numbers: Vec<u64> = (0..len)
+            .map(|_| match between(rng, 0, 2) {
+                0 => rng.between(1, 9),
+                _ => rng.between(1, 999),
+            })
+            .collect();
+
+        let Some(mut value) = numbers[1..]
+            .iter()
+            .try_fold(numbers[0], |acc, &n| match between(rng, 0, 2) {
+                0 => acc.checked_add(n),
+                1 => acc.checked_mul(n),
+                _ => format!("{acc}{n}").parse().ok(),
+            })
+            .filter(|&value| value < LIMIT)
+        else {
+            continue;
+        };
+        if rng.chance(0.5) {
+            value += rng.between(1, 1000);
+        }
+
+        let numbers: Vec<String> = numbers.iter().map(u64::to_string).collect();
+        equations.push(format!("{value}: {}", numbers.join(" ")));
+    }
+    equations.join("\n")
+}
@@ -0,0 +1,31 @@
+//! A city map with three or four antennas per frequency. Frequencies are lowercase letters,
+//! uppercase letters and digits.
+use super::between;
+use crate::check::Rng;
+use crate::grid::{Grid, Point};
+
+pub const UNIT: &str = "rows and columns";
+pub const DEFAULT_SIZE: usize = 50;
+pub const MIN_SIZE: usize = 2;
+
+const FREQUENCIES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut grid = Grid::new(size, size, '.');
+    let mut free: Vec<Point> = grid.points().collect();
+    rng.shuffle(&mut free);
+
+    let mut frequencies: Vec<char> = FREQUENCIES.chars().collect();
+    rng.shuffle(&mut frequencies);
+    frequencies.truncate((size * 3 / 4).clamp(1, FREQUENCIES.len()));
+
+    for frequency in frequencies {
+        for _ in 0..between(rng, 3, 4) {
+            let Some(pos) = free.pop() else {
+                return grid.to_string();
+            };
+            grid[pos] = frequency;
+        }
+    }
+    grid.to_string()
+}
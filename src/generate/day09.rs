@@ -0,0 +1,15 @@
+//! A disk map of `size` files. Files take one to nine blocks, the gaps between them zero to nine.
+use crate::check::Rng;
+
+pub const UNIT: &str = "files";
+pub const DEFAULT_SIZE: usize = 10000;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size * 2 - 1)
+        .map(|i| {
+            let min = if i % 2 == 0 { 1 } else { 0 };
+            char::from_digit(rng.between(min, 9) as u32, 10).unwrap()
+        })
+        .collect()
+}
@@ -0,0 +1,29 @@
+//! A topographic map of hills: heights fall off by one per step from scattered peaks of height
+//! 9, so there are hiking trails to find. Some cells are noise.
+use super::between;
+use crate::check::Rng;
+use crate::grid::{Grid, Point};
+
+pub const UNIT: &str = "rows and columns";
+pub const DEFAULT_SIZE: usize = 50;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let peaks: Vec<Point> = (0..(size * size / 40).max(1))
+        .map(|_| {
+            Point::new(
+                between(rng, 0, size - 1) as i32,
+                between(rng, 0, size - 1) as i32,
+            )
+        })
+        .collect();
+
+    Grid::from_fn(size, size, |pos| {
+        if rng.chance(0.1) {
+            return rng.between(0, 9);
+        }
+        let distance = peaks.iter().map(|peak| pos.manhattan(*peak)).min().unwrap();
+        (9 - distance.min(9)) as u64
+    })
+    .to_string()
+}
@@ -0,0 +1,16 @@
+//! A row of stones engraved with numbers of up to six digits.
+use crate::check::Rng;
+
+pub const UNIT: &str = "stones";
+pub const DEFAULT_SIZE: usize = 8;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let digits = rng.between(1, 6) as u32;
+            rng.between(0, 10u64.pow(digits)).to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,30 @@
+//! A garden of irregular regions: every plot takes the plant type of the closest of a few
+//! randomly placed seeds. Neighbouring regions can share a plant type.
+use super::between;
+use crate::check::Rng;
+use crate::grid::{Grid, Point};
+
+pub const UNIT: &str = "rows and columns";
+pub const DEFAULT_SIZE: usize = 140;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let seeds: Vec<(Point, char)> = (0..(size * size / 50).max(1))
+        .map(|_| {
+            let pos = Point::new(
+                between(rng, 0, size - 1) as i32,
+                between(rng, 0, size - 1) as i32,
+            );
+            (pos, (b'A' + rng.between(0, 25) as u8) as char)
+        })
+        .collect();
+
+    Grid::from_fn(size, size, |pos| {
+        seeds
+            .iter()
+            .min_by_key(|(seed, _)| pos.manhattan(*seed))
+            .unwrap()
+            .1
+    })
+    .to_string()
+}
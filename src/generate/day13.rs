@@ -0,0 +1,35 @@
+//! Claw machines with two buttons each. About half of the prizes can be reached with at most
+//! 100 presses per button. The buttons of a machine never move the claw in the same direction.
+use super::between;
+use crate::check::Rng;
+
+pub const UNIT: &str = "machines";
+pub const DEFAULT_SIZE: usize = 320;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut machines = vec![];
+    while machines.len() < size {
+        let a = (between(rng, 10, 99), between(rng, 10, 99));
+        let b = (between(rng, 10, 99), between(rng, 10, 99));
+        if a.0 * b.1 == a.1 * b.0 {
+            continue;
+        }
+
+        let prize = if rng.chance(0.5) {
+            let (presses_a, presses_b) = (between(rng, 1, 100), between(rng, 1, 100));
+            (
+                presses_a * a.0 + presses_b * b.0,
+                presses_a * a.1 + presses_b * b.1,
+            )
+        } else {
+            (between(rng, 1000, 20000), between(rng, 1000, 20000))
+        };
+
+        machines.push(format!(
+            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}",
+            a.0, a.1, b.0, b.1, prize.0, prize.1
+        ));
+    }
+    machines.join("\n\n")
+}
@@ -0,0 +1,58 @@
+//! Robots on the 101×103 floor. At one moment, most of them line up in a framed Christmas tree,
+//! the rest are scattered at random.
+use super::{between, signed};
+use crate::check::Rng;
+use crate::grid::Point;
+
+pub const UNIT: &str = "robots";
+pub const DEFAULT_SIZE: usize = 500;
+/// Fewer robots rarely draw a tree that stands out of the scattered rest.
+pub const MIN_SIZE: usize = 20;
+
+const WIDTH: i64 = 101;
+const HEIGHT: i64 = 103;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let offset = Point::new(between(rng, 0, 69) as i32, between(rng, 0, 69) as i32);
+    let mut positions: Vec<Point> = picture().into_iter().map(|pos| pos + offset).collect();
+    rng.shuffle(&mut positions);
+    positions.truncate(size * 2 / 3);
+    while positions.len() < size {
+        positions.push(Point::new(
+            between(rng, 0, WIDTH as usize - 1) as i32,
+            between(rng, 0, HEIGHT as usize - 1) as i32,
+        ));
+    }
+
+    let seconds = signed(rng, 1, WIDTH * HEIGHT - 1);
+    positions
+        .iter()
+        .map(|pos| {
+            let (vx, vy) = (signed(rng, -99, 99), signed(rng, -99, 99));
+            let x = (i64::from(pos.x) - vx * seconds).rem_euclid(WIDTH);
+            let y = (i64::from(pos.y) - vy * seconds).rem_euclid(HEIGHT);
+            format!("p={x},{y} v={vx},{vy}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The cells of a 31×33 frame around a tree of three tiers on a trunk.
+fn picture() -> Vec<Point> {
+    let (width, height, center): (i32, i32, i32) = (31, 33, 15);
+    let mut cells = vec![];
+    for y in 0..height {
+        let half_width = match y {
+            2..=28 => ((y - 2) % 9 + (y - 2) / 9 * 2) / 2,
+            29..=30 => 1,
+            _ => -1,
+        };
+        for x in 0..width {
+            let frame = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+            if frame || (x - center).abs() <= half_width {
+                cells.push(Point::new(x, y));
+            }
+        }
+    }
+    cells
+}
@@ -0,0 +1,39 @@
+//! A walled warehouse with boxes `O`, the robot `@` and its moves, a thousand per line.
+use super::between;
+use crate::check::Rng;
+use crate::grid::{Grid, Point};
+
+pub const UNIT: &str = "rows and columns";
+pub const DEFAULT_SIZE: usize = 50;
+pub const MIN_SIZE: usize = 3;
+
+/// Moves per row of the move list.
+const LINE_WIDTH: usize = 1000;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let last = size as i32 - 1;
+    let mut grid = Grid::from_fn(size, size, |pos| {
+        if pos.x == 0 || pos.y == 0 || pos.x == last || pos.y == last || rng.chance(0.05) {
+            '#'
+        } else if rng.chance(0.4) {
+            'O'
+        } else {
+            '.'
+        }
+    });
+    let robot = Point::new(
+        between(rng, 1, size - 2) as i32,
+        between(rng, 1, size - 2) as i32,
+    );
+    grid[robot] = '@';
+
+    let moves: Vec<char> = (0..size * 400)
+        .map(|_| *rng.choose(&['^', 'v', '<', '>']))
+        .collect();
+    let moves: Vec<String> = moves
+        .chunks(LINE_WIDTH)
+        .map(|line| line.iter().collect())
+        .collect();
+
+    format!("{grid}\n\n{}", moves.join("\n"))
+}
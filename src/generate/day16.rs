@@ -0,0 +1,26 @@
+//! A reindeer maze with the start `S` in the bottom left and the end `E` in the top right
+//! corner. A few walls are knocked out so there are several best paths.
+use super::maze;
+use crate::check::Rng;
+use crate::grid::Point;
+
+pub const UNIT: &str = "rows and columns, rounded up to an odd number";
+pub const DEFAULT_SIZE: usize = 141;
+pub const MIN_SIZE: usize = 5;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size | 1;
+    let mut grid = maze(rng, size);
+    let last = size as i32 - 1;
+
+    for pos in grid.points().collect::<Vec<_>>() {
+        let inner = pos.x > 0 && pos.y > 0 && pos.x < last && pos.y < last;
+        if inner && (pos.x + pos.y) % 2 == 1 && rng.chance(0.05) {
+            grid[pos] = '.';
+        }
+    }
+
+    grid[Point::new(1, last - 1)] = 'S';
+    grid[Point::new(last - 1, 1)] = 'E';
+    grid.to_string()
+}
@@ -0,0 +1,51 @@
+//! A three-bit program of the shape real inputs have: each iteration outputs a value computed
+//! from the lowest bits of register A, then shifts A right by three. The constants are chosen so
+//! that part two has an answer.
+use std::ops::RangeInclusive;
+
+use crate::check::Rng;
+
+pub const UNIT: &str = "octal digits of register A";
+pub const DEFAULT_SIZE: usize = 9;
+pub const MIN_SIZE: usize = 1;
+
+/// Register A holds at most 21 octal digits.
+const DIGITS: RangeInclusive<usize> = MIN_SIZE..=21;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let program = loop {
+        let (x, y, z) = (rng.between(0, 7), rng.between(0, 7), rng.between(0, 7));
+        let program = [2, 4, 1, x, 7, 5, 1, y, 4, z, 0, 3, 5, 5, 3, 0];
+        if quine_exists(&program, x, y) {
+            break program;
+        }
+    };
+
+    let digits = size.clamp(*DIGITS.start(), *DIGITS.end()) as u32;
+    let a = rng.between(8u64.pow(digits - 1), 8u64.pow(digits) - 1);
+    let program: Vec<String> = program.iter().map(u64::to_string).collect();
+    format!(
+        "Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+        program.join(",")
+    )
+}
+
+/// The value one iteration of the program outputs for register A.
+fn output(a: u64, x: u64, y: u64) -> u64 {
+    let b = (a % 8) ^ x;
+    (b ^ y ^ (a >> b)) % 8
+}
+
+/// Whether some value of register A makes the program output itself. Builds A three bits at a
+/// time, starting with the last output.
+fn quine_exists(program: &[u64], x: u64, y: u64) -> bool {
+    let mut candidates = vec![0];
+    for &expected in program.iter().rev() {
+        candidates = candidates
+            .iter()
+            .flat_map(|a| (0..8).map(move |bits| a << 3 | bits))
+            .filter(|&a| output(a, x, y) == expected)
+            .collect();
+    }
+    !candidates.is_empty()
+}
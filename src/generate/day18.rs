@@ -0,0 +1,48 @@
+//! Bytes falling into the 71×71 memory space. The exit is reachable after the first kilobyte
+//! has fallen, later bytes cut it off. At most every free cell but the corners falls once.
+use crate::check::Rng;
+use crate::graph;
+use crate::grid::{Grid, Point};
+
+pub const UNIT: &str = "bytes";
+pub const DEFAULT_SIZE: usize = 3450;
+/// Part one lets the first kilobyte fall.
+pub const MIN_SIZE: usize = 1024;
+
+const SIZE: usize = 71;
+const FIRST: usize = 1024;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let (start, exit) = (Point::ORIGIN, Point::new(SIZE as i32 - 1, SIZE as i32 - 1));
+    let mut bytes: Vec<Point> = Grid::new(SIZE, SIZE, ())
+        .points()
+        .filter(|&pos| pos != start && pos != exit)
+        .collect();
+
+    loop {
+        rng.shuffle(&mut bytes);
+        let mut grid = Grid::new(SIZE, SIZE, false);
+        for &pos in &bytes[..FIRST] {
+            grid[pos] = true;
+        }
+        let path = graph::bfs(
+            [start],
+            |&pos| {
+                grid.neighbours4(pos)
+                    .filter(|&next| !grid[next])
+                    .collect::<Vec<_>>()
+            },
+            |&pos| pos == exit,
+        );
+        if path.is_some() {
+            break;
+        }
+    }
+
+    bytes
+        .iter()
+        .take(size)
+        .map(|pos| format!("{},{}", pos.x, pos.y))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,46 @@
+//! Towel patterns of one to eight stripes and designs of twenty to sixty stripes. About half of
+//! the designs are made from the patterns, the others are random stripes.
+use std::collections::BTreeSet;
+
+use super::between;
+use crate::check::Rng;
+
+pub const UNIT: &str = "designs";
+pub const DEFAULT_SIZE: usize = 400;
+pub const MIN_SIZE: usize = 1;
+
+const COLORS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+const PATTERNS: usize = 447;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // NOTE: without one of the single stripe towels, some random designs are impossible.
+    let missing = *rng.choose(&COLORS);
+    let mut patterns = BTreeSet::new();
+    while patterns.len() < PATTERNS {
+        let len = between(rng, 1, 8);
+        let pattern: String = (0..len).map(|_| *rng.choose(&COLORS)).collect();
+        if pattern != missing.to_string() {
+            patterns.insert(pattern);
+        }
+    }
+    let mut patterns: Vec<String> = patterns.into_iter().collect();
+    rng.shuffle(&mut patterns);
+
+    let designs: Vec<String> = (0..size)
+        .map(|_| {
+            let len = between(rng, 20, 60);
+            let mut design = String::new();
+            let possible = rng.chance(0.5);
+            while design.len() < len {
+                if possible {
+                    design.push_str(rng.choose(&patterns).as_str());
+                } else {
+                    design.push(*rng.choose(&COLORS));
+                }
+            }
+            design
+        })
+        .collect();
+
+    format!("{}\n\n{}", patterns.join(", "), designs.join("\n"))
+}
@@ -0,0 +1,40 @@
+//! A racetrack: a single path without branches from `S` to `E` through a field of walls. The
+//! path is the longest one through a random maze.
+use std::collections::HashMap;
+
+use super::maze;
+use crate::check::Rng;
+use crate::graph;
+use crate::grid::{Grid, Point};
+
+pub const UNIT: &str = "rows and columns, rounded up to an odd number";
+pub const DEFAULT_SIZE: usize = 141;
+pub const MIN_SIZE: usize = 5;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let maze = maze(rng, size | 1);
+    let successors = |pos: &Point| {
+        maze.neighbours4(*pos)
+            .filter(|&next| maze[next] == '.')
+            .collect::<Vec<_>>()
+    };
+    let farthest = |distances: HashMap<Point, usize>| {
+        distances
+            .into_iter()
+            .max_by_key(|&(pos, distance)| (distance, pos))
+            .unwrap()
+            .0
+    };
+
+    let start = farthest(graph::bfs_distances([Point::new(1, 1)], successors));
+    let end = farthest(graph::bfs_distances([start], successors));
+    let track = graph::bfs([start], successors, |&pos| pos == end).unwrap();
+
+    let mut grid = Grid::new(maze.width(), maze.height(), '#');
+    for pos in track {
+        grid[pos] = '.';
+    }
+    grid[start] = 'S';
+    grid[end] = 'E';
+    grid.to_string()
+}
@@ -0,0 +1,13 @@
+//! Door codes of three digits followed by `A`.
+use crate::check::Rng;
+
+pub const UNIT: &str = "codes";
+pub const DEFAULT_SIZE: usize = 5;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{:03}A", rng.between(1, 999)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
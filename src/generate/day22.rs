@@ -0,0 +1,13 @@
+//! The initial secret numbers of the buyers.
+use crate::check::Rng;
+
+pub const UNIT: &str = "buyers";
+pub const DEFAULT_SIZE: usize = 2000;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| rng.between(1, 0xFF_FFFF).to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,45 @@
+//! A network map of computers with two-letter names. Every computer has about thirteen random
+//! connections, and thirteen of them form the LAN party: a set of computers that are all
+//! connected to each other.
+use std::collections::BTreeSet;
+
+use super::{between, names};
+use crate::check::Rng;
+
+pub const UNIT: &str = "computers";
+pub const DEFAULT_SIZE: usize = 520;
+pub const MIN_SIZE: usize = PARTY;
+
+const PARTY: usize = 13;
+const DEGREE: usize = 13;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // NOTE: there are only so many two-letter names.
+    let size = size.min(26 * 26);
+    let computers = names(rng, size, 2, "");
+    let mut connections = BTreeSet::new();
+
+    for a in 0..PARTY {
+        for b in a + 1..PARTY {
+            connections.insert((a, b));
+        }
+    }
+    for a in PARTY..size {
+        for _ in 0..DEGREE / 2 {
+            let b = between(rng, 0, size - 1);
+            if a != b {
+                connections.insert((a.min(b), a.max(b)));
+            }
+        }
+    }
+
+    let mut lines: Vec<String> = connections
+        .into_iter()
+        .map(|(a, b)| {
+            let (a, b) = if rng.chance(0.5) { (a, b) } else { (b, a) };
+            format!("{}-{}", computers[a], computers[b])
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.join("\n")
+}
@@ -0,0 +1,87 @@
+//! A ripple-carry adder for two `size`-bit numbers with four pairs of swapped gate outputs. The
+//! swaps are of the kinds found in real inputs: an output bit with a wire of the same bit, or
+//! the two gates combining the input bits. Part two expects 45-bit numbers.
+use super::{between, names};
+use crate::check::Rng;
+
+pub const UNIT: &str = "bits";
+pub const DEFAULT_SIZE: usize = 45;
+/// Four swaps in distinct bits, none in the first two or the last one.
+pub const MIN_SIZE: usize = 7;
+
+const SWAPS: usize = 4;
+
+struct Gate {
+    left: String,
+    op: &'static str,
+    right: String,
+    output: String,
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut wires = names(rng, 4 * size, 3, "xyz").into_iter();
+    let mut wire = || wires.next().unwrap();
+    let bit = |name: char, i: usize| format!("{name}{i:02}");
+
+    // NOTE: per bit: x XOR y -> sum, x AND y -> and, sum XOR carry -> z, sum AND carry -> and2,
+    // and OR and2 -> next carry. Bit 0 has no incoming carry.
+    let mut gates = vec![];
+    let mut gate = |left: String, op, right: String, output: String| {
+        gates.push(Gate {
+            left,
+            op,
+            right,
+            output,
+        })
+    };
+    gate(bit('x', 0), "XOR", bit('y', 0), bit('z', 0));
+    let mut carry = wire();
+    gate(bit('x', 0), "AND", bit('y', 0), carry.clone());
+    for i in 1..size {
+        let (sum, and, and2) = (wire(), wire(), wire());
+        let next = if i == size - 1 {
+            bit('z', size)
+        } else {
+            wire()
+        };
+        gate(bit('x', i), "XOR", bit('y', i), sum.clone());
+        gate(bit('x', i), "AND", bit('y', i), and.clone());
+        gate(sum.clone(), "XOR", carry.clone(), bit('z', i));
+        gate(sum, "AND", carry, and2.clone());
+        gate(and, "OR", and2, next.clone());
+        carry = next;
+    }
+
+    let mut bits: Vec<usize> = (2..size - 1).collect();
+    rng.shuffle(&mut bits);
+    for &i in bits.iter().take(SWAPS) {
+        // NOTE: gates of bit i are at 5 * i - 3.. in the order they were added above.
+        let first = 5 * i - 3;
+        let (a, b) = match between(rng, 0, 3) {
+            0 => (first + 2, first + 1),
+            1 => (first + 2, first + 3),
+            2 => (first + 2, first + 4),
+            _ => (first, first + 1),
+        };
+        let output = std::mem::take(&mut gates[a].output);
+        gates[a].output = std::mem::replace(&mut gates[b].output, output);
+    }
+
+    let mut lines: Vec<String> = ['x', 'y']
+        .into_iter()
+        .flat_map(|name| (0..size).map(move |i| bit(name, i)))
+        .map(|input| format!("{input}: {}", rng.between(0, 1)))
+        .collect();
+    lines.push(String::new());
+
+    rng.shuffle(&mut gates);
+    lines.extend(gates.iter().map(|gate| {
+        let (left, right) = if rng.chance(0.5) {
+            (&gate.left, &gate.right)
+        } else {
+            (&gate.right, &gate.left)
+        };
+        format!("{left} {} {right} -> {}", gate.op, gate.output)
+    }));
+    lines.join("\n")
+}
@@ -0,0 +1,37 @@
+//! Schematics of locks and keys, about half of each. Locks have their top row filled, keys
+//! their bottom row.
+use crate::check::Rng;
+
+pub const UNIT: &str = "schematics";
+pub const DEFAULT_SIZE: usize = 500;
+pub const MIN_SIZE: usize = 1;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let lock = rng.chance(0.5);
+            let heights: Vec<usize> = (0..5).map(|_| rng.between(0, 5) as usize).collect();
+            (0..7)
+                .map(|row| {
+                    heights
+                        .iter()
+                        .map(|&height| {
+                            let filled = if lock {
+                                row <= height
+                            } else {
+                                row >= 6 - height
+                            };
+                            if filled {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
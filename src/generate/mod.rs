@@ -0,0 +1,201 @@
+//! Synthetic puzzle inputs.
+//!
+//! Real inputs may not be shared, so CI and contributors without an account can only run the
+//! examples. Every day has a generator that produces a valid input in that day's format, sized
+//! like a real one by default. The same seed and size always produce the same input:
+//!
+//! ```no_run
+//! use advent_of_code::{day, generate};
+//!
+//! let input = generate::input(day!(6), 2024, None);
+//! ```
+//!
+//! The generators also serve as input sources for [`crate::check::differential`].
+use crate::check::Rng;
+use crate::grid::{Grid, Point};
+use crate::template::Day;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+/// Seed used when none is given.
+pub const DEFAULT_SEED: u64 = 2024;
+
+/// The input generator of a day.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    /// What the size passed to `generate` counts, e.g. "reports".
+    pub unit: &'static str,
+    /// Size of a real puzzle input.
+    pub default_size: usize,
+    /// Smallest size for which the generated input is valid.
+    pub min_size: usize,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+/// Returns the generator for `day`.
+pub fn generator(day: Day) -> Generator {
+    macro_rules! generator {
+        ($module:ident) => {
+            Generator {
+                unit: $module::UNIT,
+                default_size: $module::DEFAULT_SIZE,
+                min_size: $module::MIN_SIZE,
+                generate: $module::generate,
+            }
+        };
+    }
+
+    match day.into_inner() {
+        1 => generator!(day01),
+        2 => generator!(day02),
+        3 => generator!(day03),
+        4 => generator!(day04),
+        5 => generator!(day05),
+        6 => generator!(day06),
+        7 => generator!(day07),
+        8 => generator!(day08),
+        9 => generator!(day09),
+        10 => generator!(day10),
+        11 => generator!(day11),
+        12 => generator!(day12),
+        13 => generator!(day13),
+        14 => generator!(day14),
+        15 => generator!(day15),
+        16 => generator!(day16),
+        17 => generator!(day17),
+        18 => generator!(day18),
+        19 => generator!(day19),
+        20 => generator!(day20),
+        21 => generator!(day21),
+        22 => generator!(day22),
+        23 => generator!(day23),
+        24 => generator!(day24),
+        25 => generator!(day25),
+        _ => unreachable!("days are between 1 and 25"),
+    }
+}
+
+/// Generates an input for `day`. Uses the default size if `size` is `None`.
+pub fn input(day: Day, seed: u64, size: Option<usize>) -> String {
+    let generator = generator(day);
+    let size = size.unwrap_or(generator.default_size);
+    (generator.generate)(&mut Rng::new(seed), size)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Draws a number in `low..=high`.
+fn between(rng: &mut Rng, low: usize, high: usize) -> usize {
+    rng.between(low as u64, high as u64) as usize
+}
+
+/// Draws a signed number in `low..=high`.
+fn signed(rng: &mut Rng, low: i64, high: i64) -> i64 {
+    low + rng.between(0, (high - low) as u64) as i64
+}
+
+/// Draws `count` distinct names of `len` lowercase letters, none of them starting with a letter
+/// in `reserved`.
+fn names(rng: &mut Rng, count: usize, len: u32, reserved: &str) -> Vec<String> {
+    let mut names = std::collections::BTreeSet::new();
+    let mut ordered = Vec::with_capacity(count);
+    assert!(
+        (count as u64) <= (26 - reserved.len() as u64) * 26u64.pow(len - 1),
+        "not enough {len}-letter names for {count} items"
+    );
+    while ordered.len() < count {
+        let name: String = (0..len)
+            .map(|_| (b'a' + rng.between(0, 25) as u8) as char)
+            .collect();
+        if !name.starts_with(|c| reserved.contains(c)) && names.insert(name.clone()) {
+            ordered.push(name);
+        }
+    }
+    ordered
+}
+
+/// Carves a maze without loops into a `size`×`size` grid of walls with a randomized depth-first
+/// search. Passages run along odd coordinates, so `size` should be odd.
+fn maze(rng: &mut Rng, size: usize) -> Grid<char> {
+    let size = size as i32;
+    let mut grid = Grid::new(size as usize, size as usize, '#');
+    let start = Point::new(1, 1);
+    grid[start] = '.';
+    let mut stack = vec![start];
+
+    while let Some(&current) = stack.last() {
+        let mut directions = Point::ORTHOGONAL;
+        rng.shuffle(&mut directions);
+        let next = directions.into_iter().find(|&dir| {
+            let next = current + dir * 2;
+            next.x > 0 && next.y > 0 && next.x < size - 1 && next.y < size - 1 && grid[next] == '#'
+        });
+        match next {
+            Some(dir) => {
+                grid[current + dir] = '.';
+                grid[current + dir * 2] = '.';
+                stack.push(current + dir * 2);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    grid
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generator, input};
+    use crate::template::all_days;
+
+    #[test]
+    fn generates_reproducible_inputs() {
+        for day in all_days() {
+            let generator = generator(day);
+            let size = generator.min_size.max(generator.default_size / 10);
+            assert_eq!(input(day, 7, Some(size)), input(day, 7, Some(size)));
+            assert!(!input(day, 7, Some(size)).is_empty(), "day {day} is empty");
+        }
+    }
+
+    #[test]
+    fn varies_with_seed() {
+        for day in all_days() {
+            let generator = generator(day);
+            let size = generator.min_size.max(generator.default_size / 10);
+            assert_ne!(
+                input(day, 1, Some(size)),
+                input(day, 2, Some(size)),
+                "day {day}"
+            );
+        }
+    }
+}
@@ -0,0 +1,471 @@
+//! Graph searches over a successor function, for mazes and other implicit graphs.
+//!
+//! Nodes can be anything hashable, e.g. a [`Point`](crate::grid::Point) or a tuple of a
+//! position and a direction. Searches take one or more start nodes and a function returning
+//! the neighbours of a node, with their costs for the weighted searches.
+//!
+//! ```
+//! use advent_of_code::graph;
+//! use advent_of_code::grid::{Grid, Point};
+//!
+//! let grid = Grid::parse("S.#\n..#\n#.E");
+//! let start = grid.position_of(&'S').unwrap();
+//! let end = grid.position_of(&'E').unwrap();
+//!
+//! let path = graph::bfs(
+//!     [start],
+//!     |p| grid.neighbours4(*p).filter(|n| grid[*n] != '#').collect::<Vec<_>>(),
+//!     |p| *p == end,
+//! );
+//! assert_eq!(path.map(|path| path.len() - 1), Some(4));
+//! ```
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Finds a path with the fewest steps from one of `starts` to a node matching `goal`.
+///
+/// The path includes both the start and the goal node.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !parents.contains_key(&start) {
+            parents.insert(start.clone(), None);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            return Some(reconstruct(node, |n| parents[n].as_ref()));
+        }
+        for next in successors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the number of steps from the closest of `starts` to every reachable node.
+pub fn bfs_distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Finds a cheapest path from one of `starts` to a node matching `goal`, returning it with its
+/// cost.
+///
+/// `successors` returns the neighbours of a node with the cost of moving there. Costs must not
+/// be negative; `C::default()` is the cost of standing still.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), goal)
+}
+
+/// Like [`dijkstra`], but explores nodes in the order of their cost plus `heuristic`.
+///
+/// The heuristic estimates the remaining cost to the goal. It must never overestimate it,
+/// otherwise the path found may not be the cheapest one.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: HashMap<N, C> = HashMap::new();
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        costs.insert(start.clone(), C::default());
+        heap.push(Entry {
+            cost: C::default(),
+            estimate: heuristic(&start),
+            node: start,
+        });
+    }
+
+    while let Some(Entry { cost, node, .. }) = heap.pop() {
+        // NOTE: a node is pushed again whenever a cheaper way to it is found; skip stale entries.
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+        if goal(&node) {
+            return Some((reconstruct(node, |n| parents.get(n)), cost));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Entry {
+                    cost: next_cost,
+                    estimate: next_cost + heuristic(&next),
+                    node: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Runs Dijkstra's algorithm over every node reachable from `starts`, keeping all
+/// predecessors on cheapest paths.
+///
+/// Unlike [`dijkstra`], this answers questions about all shortest paths, e.g. which nodes lie
+/// on any of them. Costs must not be negative. Zero-cost edges are fine: a start node never gets
+/// a parent, so the paths can't loop back through it.
+pub fn shortest_paths<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: HashMap<N, C> = HashMap::new();
+    let mut parents: HashMap<N, Vec<N>> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    let mut start_nodes = HashSet::new();
+
+    for start in starts {
+        costs.insert(start.clone(), C::default());
+        start_nodes.insert(start.clone());
+        heap.push(Entry {
+            cost: C::default(),
+            estimate: C::default(),
+            node: start,
+        });
+    }
+
+    while let Some(Entry { cost, node, .. }) = heap.pop() {
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match costs.get(&next).map(|best| next_cost.cmp(best)) {
+                Some(Ordering::Greater) => {}
+                Some(Ordering::Equal) if start_nodes.contains(&next) => {}
+                Some(Ordering::Equal) => parents.entry(next).or_default().push(node.clone()),
+                None | Some(Ordering::Less) => {
+                    costs.insert(next.clone(), next_cost);
+                    parents.insert(next.clone(), vec![node.clone()]);
+                    heap.push(Entry {
+                        cost: next_cost,
+                        estimate: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+    }
+
+    ShortestPaths { costs, parents }
+}
+
+/// The cheapest paths from a set of start nodes to every reachable node, see [`shortest_paths`].
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, C> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, Vec<N>>,
+}
+
+impl<N, C> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy,
+{
+    /// The cost of reaching `node`, or `None` if it can't be reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The cost of reaching each reachable node.
+    pub fn costs(&self) -> &HashMap<N, C> {
+        &self.costs
+    }
+
+    /// The nodes directly preceding `node` on its cheapest paths.
+    pub fn parents(&self, node: &N) -> &[N] {
+        self.parents.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One of the cheapest paths to `node`, starting with a start node.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.costs
+            .contains_key(node)
+            .then(|| reconstruct(node.clone(), |n| self.parents(n).first()))
+    }
+
+    /// Every node lying on any of the cheapest paths to one of `targets`, including the targets.
+    pub fn nodes_on_paths_to(&self, targets: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let targets = targets.into_iter().filter(|t| self.costs.contains_key(t));
+        flood_fill(targets, |node| self.parents(node).to_vec())
+    }
+}
+
+/// Returns every node reachable from `starts`, including the starts.
+pub fn flood_fill<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut stack: Vec<N> = starts.into_iter().collect();
+
+    while let Some(node) = stack.pop() {
+        if seen.insert(node.clone()) {
+            stack.extend(successors(&node).into_iter().filter(|n| !seen.contains(n)));
+        }
+    }
+
+    seen
+}
+
+/// Splits `nodes` into groups connected through `successors`, in the order of their first node.
+///
+/// `successors` should be symmetric, otherwise the groups depend on the order of `nodes`.
+pub fn components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+
+    for start in nodes {
+        if !seen.insert(start.clone()) {
+            continue;
+        }
+
+        let mut component = vec![];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for next in successors(&node) {
+                if seen.insert(next.clone()) {
+                    stack.push(next);
+                }
+            }
+            component.push(node);
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+/// Follows `parent` from `end` back to a start node, returning the path in walking order.
+fn reconstruct<'a, N: Clone + 'a>(end: N, mut parent: impl FnMut(&N) -> Option<&'a N>) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(prev) = parent(path.last().unwrap()) {
+        path.push(prev.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// A node on the open list, ordered so the [`BinaryHeap`] pops the lowest estimate first.
+struct Entry<N, C> {
+    cost: C,
+    estimate: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // NOTE: on equal estimates, prefer the node that got further, it is closer to the goal.
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_distances, components, dijkstra, flood_fill, shortest_paths};
+    use crate::grid::{Grid, Point};
+
+    const MAZE: &str = "\
+S...#
+.##.#
+.#...
+...#E";
+
+    fn open(grid: &Grid<char>, p: &Point) -> Vec<Point> {
+        grid.neighbours4(*p).filter(|n| grid[*n] != '#').collect()
+    }
+
+    #[test]
+    fn finds_fewest_steps() {
+        let grid = Grid::parse(MAZE);
+        let end = grid.position_of(&'E').unwrap();
+
+        let path = bfs([Point::ORIGIN], |p| open(&grid, p), |p| *p == end).unwrap();
+        assert_eq!(path.len() - 1, 7);
+        assert_eq!(path.first(), Some(&Point::ORIGIN));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        assert_eq!(bfs([Point::ORIGIN], |p| open(&grid, p), |_| false), None);
+        assert_eq!(bfs_distances([end], |p| open(&grid, p))[&Point::ORIGIN], 7);
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        // NOTE: moving down is expensive, so the path goes around through the top row.
+        let grid = Grid::parse(MAZE);
+        let end = grid.position_of(&'E').unwrap();
+        let successors = |p: &Point| {
+            open(&grid, p)
+                .into_iter()
+                .map(|n| (n, if n.y > p.y { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+
+        let (path, cost) = dijkstra([Point::ORIGIN], successors, |p| *p == end).unwrap();
+        assert_eq!(cost, 34);
+        assert_eq!(path[1], Point::new(1, 0));
+
+        let (path, cost) = astar(
+            [Point::ORIGIN],
+            successors,
+            |p| p.manhattan(end),
+            |p| *p == end,
+        )
+        .unwrap();
+        assert_eq!((path.len(), cost), (8, 34));
+    }
+
+    #[test]
+    fn keeps_all_shortest_paths() {
+        let grid = Grid::parse("S..\n...\n..E");
+        let end = Point::new(2, 2);
+        let paths = shortest_paths([Point::ORIGIN], |p: &Point| {
+            open(&grid, p)
+                .into_iter()
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(paths.cost(&end), Some(4));
+        assert_eq!(paths.parents(&end).len(), 2);
+        assert_eq!(paths.path_to(&end).map(|path| path.len()), Some(5));
+        assert_eq!(paths.nodes_on_paths_to([end]).len(), 9);
+        assert_eq!(paths.nodes_on_paths_to([Point::new(1, 0)]).len(), 2);
+    }
+
+    #[test]
+    fn ignores_zero_cost_cycles_through_starts() {
+        let edges = |node: &char| match node {
+            'A' => vec![('B', 0), ('C', 1)],
+            'B' => vec![('A', 0), ('C', 1)],
+            _ => vec![],
+        };
+        let paths = shortest_paths(['A'], edges);
+
+        assert_eq!(paths.parents(&'A'), []);
+        assert_eq!(paths.path_to(&'B'), Some(vec!['A', 'B']));
+        assert_eq!(paths.parents(&'C'), ['A', 'B']);
+        assert_eq!(paths.nodes_on_paths_to(['A']).len(), 1);
+        assert_eq!(paths.nodes_on_paths_to(['C']).len(), 3);
+    }
+
+    #[test]
+    fn groups_connected_nodes() {
+        let grid = Grid::parse("aab\nbab\nbba");
+        let same = |p: &Point| {
+            grid.neighbours4(*p)
+                .filter(|n| grid[*n] == grid[*p])
+                .collect::<Vec<_>>()
+        };
+
+        let sizes: Vec<usize> = components(grid.points(), same)
+            .iter()
+            .map(Vec::len)
+            .collect();
+        assert_eq!(sizes, [3, 2, 3, 1]);
+        assert_eq!(flood_fill([Point::new(2, 0)], same).len(), 2);
+    }
+}
@@ -0,0 +1,572 @@
+//! A rectangular 2D grid with flat storage, for the many puzzles drawn on a map.
+//!
+//! ```
+//! use advent_of_code::grid::{Grid, Point};
+//!
+//! let grid = Grid::parse("#..\n.#.\n..S");
+//! let start = grid.position_of(&'S').unwrap();
+//! assert_eq!(start, Point::new(2, 2));
+//! assert_eq!(grid.neighbours4(start).filter(|p| grid[*p] == '.').count(), 2);
+//! ```
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+};
+
+/// A position or offset on a [`Grid`]. `x` grows to the right, `y` grows downwards.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+    pub const RIGHT: Point = Point::new(1, 0);
+
+    /// The four orthogonal directions, clockwise from [`Point::UP`].
+    pub const ORTHOGONAL: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+
+    /// The four diagonal directions, clockwise from up-right.
+    pub const DIAGONAL: [Point; 4] = [
+        Point::new(1, -1),
+        Point::new(1, 1),
+        Point::new(-1, 1),
+        Point::new(-1, -1),
+    ];
+
+    /// All eight directions, clockwise from [`Point::UP`].
+    pub const ALL: [Point; 8] = [
+        Point::UP,
+        Point::new(1, -1),
+        Point::RIGHT,
+        Point::new(1, 1),
+        Point::DOWN,
+        Point::new(-1, 1),
+        Point::LEFT,
+        Point::new(-1, -1),
+    ];
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the direction an arrow (`^`, `>`, `v` or `<`) points to.
+    pub fn from_arrow(arrow: char) -> Option<Self> {
+        match arrow {
+            '^' => Some(Point::UP),
+            '>' => Some(Point::RIGHT),
+            'v' => Some(Point::DOWN),
+            '<' => Some(Point::LEFT),
+            _ => None,
+        }
+    }
+
+    /// The direction rotated by 90° clockwise.
+    pub const fn turn_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// The direction rotated by 90° counter-clockwise.
+    pub const fn turn_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// The taxicab distance between two points.
+    pub const fn manhattan(self, other: Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The four orthogonally adjacent points, which may lie outside of a grid.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Point::ORTHOGONAL.into_iter().map(move |dir| self + dir)
+    }
+
+    /// The eight surrounding points, which may lie outside of a grid.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Point::ALL.into_iter().map(move |dir| self + dir)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i32) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// Parses a grid of characters, one row per line.
+    ///
+    /// # Panics
+    /// If the lines differ in length.
+    pub fn parse(input: &str) -> Self {
+        Self::parse_with(input, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a grid with one row per line, converting each character with `f`.
+    ///
+    /// # Panics
+    /// If the lines differ in length.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let len = cells.len() - before;
+
+            match width {
+                None => width = Some(len),
+                Some(width) => assert_eq!(
+                    width,
+                    len,
+                    "line {} is {len} characters long, expecting {width}",
+                    y + 1
+                ),
+            }
+        }
+
+        Self::from_vec(width.unwrap_or(0), cells)
+    }
+
+    /// Creates a grid from its cells, row by row.
+    ///
+    /// # Panics
+    /// If `cells` can't be split into rows of `width` cells.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells don't fill rows of {width}",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid by calling `f` for each position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..width * height)
+            .map(|i| f(Self::point_at(width, i)))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid with all cells set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// All cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Checks whether `point` lies on the grid.
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    fn point_at(width: usize, index: usize) -> Point {
+        Point::new((index % width) as i32, (index / width) as i32)
+    }
+
+    /// Returns the cell at `point`, or `None` if it lies outside of the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    /// Returns the cell at `point` mutably, or `None` if it lies outside of the grid.
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Wraps `point` around the edges, as if the grid was tiled infinitely.
+    pub fn wrap(&self, point: Point) -> Point {
+        Point::new(
+            point.x.rem_euclid(self.width as i32),
+            point.y.rem_euclid(self.height as i32),
+        )
+    }
+
+    /// Returns the cell at `point` wrapped around the edges, see [`Grid::wrap`].
+    pub fn get_wrapping(&self, point: Point) -> &T {
+        &self[self.wrap(point)]
+    }
+
+    /// All positions, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Self::point_at(width, i))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The orthogonally adjacent positions of `point` that lie on the grid.
+    ///
+    /// Looking up each position afterwards checks the bounds a second time. Hot loops are faster
+    /// with [`Point::neighbours4`] and [`Grid::get`], which check them only once.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours4().filter(|p| self.contains(*p))
+    }
+
+    /// The surrounding positions of `point` that lie on the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours8().filter(|p| self.contains(*p))
+    }
+
+    /// Returns the position of the first cell matching `predicate`, row by row.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| Self::point_at(self.width, i))
+    }
+
+    /// Returns the position of the first cell equal to `value`, row by row.
+    pub fn position_of(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.find(|cell| cell == value)
+    }
+
+    /// Returns the positions of all cells matching `predicate`, row by row.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The cells of row `y`.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// All rows, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// All columns, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Walks from `start` in steps of `step` until leaving the grid, including `start`.
+    pub fn ray(&self, start: Point, step: Point) -> impl Iterator<Item = (Point, &T)> {
+        std::iter::successors(Some(start), move |p| Some(*p + step))
+            .map_while(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// All diagonals running down to the right, starting with the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height as i32)
+            .rev()
+            .map(|y| Point::new(0, y))
+            .chain((1..self.width as i32).map(|x| Point::new(x, 0)));
+        starts.map(|start| self.ray(start, Point::new(1, 1)).map(|(_, cell)| cell))
+    }
+
+    /// All diagonals running down to the left, starting with the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let right = self.width as i32 - 1;
+        let starts = (0..self.width as i32)
+            .map(|x| Point::new(x, 0))
+            .chain((1..self.height as i32).map(move |y| Point::new(right, y)));
+        starts.map(|start| self.ray(start, Point::new(-1, 1)).map(|(_, cell)| cell))
+    }
+
+    /// Creates a grid of the same size by converting every cell with `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let bottom = self.height as i32 - 1;
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, bottom - p.x)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90° counter-clockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let right = self.width as i32 - 1;
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(right - p.y, p.x)].clone()
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.index_of(point) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "point {point} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.index_of(point) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "point {point} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, Point};
+
+    const INPUT: &str = "abc\ndef";
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.to_string(), INPUT);
+
+        let digits = Grid::parse_with("12\n34", |c| c.to_digit(10).unwrap());
+        assert_eq!(digits.cells(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 is 2 characters long")]
+    fn rejects_ragged_input() {
+        Grid::parse("abc\nde");
+    }
+
+    #[test]
+    fn checked_and_wrapping_access() {
+        let mut grid = Grid::parse(INPUT);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(*grid.get_wrapping(Point::new(-1, -1)), 'f');
+        assert_eq!(*grid.get_wrapping(Point::new(4, 2)), 'b');
+
+        *grid.get_mut(Point::new(0, 0)).unwrap() = 'z';
+        grid[Point::new(1, 0)] = 'y';
+        assert_eq!(grid.row(0), ['z', 'y', 'c']);
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.neighbours4(Point::ORIGIN).count(), 2);
+        assert_eq!(grid.neighbours8(Point::ORIGIN).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.position_of(&'e'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(|c| *c > 'c'), Some(Point::new(0, 1)));
+        assert_eq!(grid.positions(|c| "ace".contains(*c)).count(), 3);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = Grid::parse(INPUT);
+        let collect = |cells: &mut dyn Iterator<Item = &char>| cells.collect::<String>();
+
+        assert_eq!(
+            grid.rows()
+                .map(|row| row.iter().collect::<String>())
+                .collect::<Vec<_>>(),
+            ["abc", "def"]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|mut c| collect(&mut c))
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.diagonals()
+                .map(|mut d| collect(&mut d))
+                .collect::<Vec<_>>(),
+            ["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|mut d| collect(&mut d))
+                .collect::<Vec<_>>(),
+            ["a", "bd", "ce", "f"]
+        );
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Point::UP.turn_right(), Point::RIGHT);
+        assert_eq!(Point::UP.turn_left(), Point::LEFT);
+        assert_eq!(Point::from_arrow('v'), Some(Point::DOWN));
+        assert_eq!(Point::new(1, 2).manhattan(Point::new(-2, 0)), 5);
+    }
+}
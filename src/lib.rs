@@ -1,3 +1,21 @@
+pub mod check;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod parse;
 pub mod template;
+pub mod trace;
+pub mod viz;
+
+mod solutions;
+
+pub use solutions::{days, solve, Answer, Solution};
+
+// NOTE: lets the solutions refer to the library as `advent_of_code` when they are compiled into it.
+extern crate self as advent_of_code;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
 
 // Use this file to add helper functions and additional modules.
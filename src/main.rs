@@ -1,22 +1,42 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use advent_of_code::template::cli;
+use advent_of_code::template::commands::{
+    all, completions, download, generate, puzzles, read, repl, scaffold, scale, serve, solve, time,
+};
+use advent_of_code::template::{config, output, Error};
+use args::{parse, AppArguments, ParsedArguments, UsageError};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        cli::{self, Shell},
+        output::ColorChoice,
+        Day, Part,
+    };
+    use advent_of_code::viz;
+    use std::{ffi::OsString, fmt::Display};
 
     pub enum AppArguments {
         Download {
             day: Day,
         },
+        Generate {
+            day: Day,
+            seed: Option<u64>,
+            size: Option<usize>,
+            overwrite: bool,
+        },
         Read {
             day: Day,
         },
+        PuzzlesSearch {
+            query: String,
+        },
+        PuzzlesShow {
+            day: Day,
+            part: Option<Part>,
+        },
         Scaffold {
             day: Day,
             download: bool,
@@ -26,119 +46,486 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            verbose: bool,
+            submit: Option<Part>,
+            variant: Option<String>,
+            trace: bool,
+            viz: Option<viz::Target>,
+            fps: Option<u64>,
         },
         All {
             release: bool,
+            inputs: Option<String>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            variants: bool,
+        },
+        Scale {
+            day: Day,
+            tile: bool,
+            sizes: Option<Vec<usize>>,
+            seed: Option<u64>,
+        },
+        Repl {
+            day: Day,
+        },
+        Serve {
+            port: Option<u16>,
+        },
+        Completions {
+            shell: Shell,
+        },
+        Help {
+            command: Option<String>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+    }
+
+    /// Arguments accepted by every command.
+    pub struct GlobalArguments {
+        /// `--config <path>`: reads the configuration from `path` instead of `aoc.toml`.
+        pub config: Option<String>,
+        /// `--set <key>=<value>`: overrides a configuration value. Can be repeated.
+        pub overrides: Vec<String>,
+        /// `--strict`: fails on unknown arguments instead of warning about them.
+        pub strict: bool,
+        /// `--color <when>`: whether to style output.
+        pub color: ColorChoice,
+    }
+
+    pub struct ParsedArguments {
+        pub global: GlobalArguments,
+        pub command: AppArguments,
+        /// Arguments that were not consumed by the command.
+        pub unknown: Vec<OsString>,
+    }
+
+    /// An error caused by invalid command-line input. Displays with the usage of the affected command.
+    #[derive(Debug)]
+    pub struct UsageError {
+        pub message: String,
+        pub command: Option<&'static cli::Command>,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    impl std::error::Error for UsageError {}
+
+    impl Display for UsageError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self.command {
+                Some(cmd) => write!(
+                    f,
+                    "{}\n\nUsage: {} {}\n\nFor more information, try `{} help {}`.",
+                    self.message,
+                    cli::BIN_NAME,
+                    cmd.usage(),
+                    cli::BIN_NAME,
+                    cmd.name
+                ),
+                None => write!(
+                    f,
+                    "{}\n\nUsage: {} [options] <command> [args]\n\nFor more information, try `{} help`.",
+                    self.message,
+                    cli::BIN_NAME,
+                    cli::BIN_NAME
+                ),
+            }
+        }
+    }
+
+    impl UsageError {
+        fn new(message: impl Into<String>, command: Option<&'static cli::Command>) -> Self {
+            Self {
+                message: message.into(),
+                command,
+            }
+        }
+
+        fn from_pico(err: pico_args::Error, command: &'static cli::Command) -> Self {
+            let message = match err {
+                pico_args::Error::MissingArgument => {
+                    let missing = command
+                        .args
+                        .iter()
+                        .find(|arg| arg.required)
+                        .map_or("argument".into(), |arg| format!("<{}>", arg.name));
+                    format!("missing required argument {missing}")
+                }
+                pico_args::Error::Utf8ArgumentParsingFailed { value, cause } => {
+                    format!("invalid value `{value}`: {cause}")
+                }
+                pico_args::Error::ArgumentParsingFailed { cause } => cause,
+                err => err.to_string(),
+            };
+            Self::new(message, Some(command))
+        }
+
+        /// Creates the error reported for unknown arguments in strict mode.
+        pub fn unknown_arguments(
+            unknown: &[OsString],
+            command: Option<&'static cli::Command>,
+        ) -> Self {
+            Self::new(format!("unknown argument(s): {unknown:?}"), command)
+        }
+    }
+
+    /// Separates flags that neither the command nor the global options know about.
+    /// Removing them upfront keeps them from being mistaken for free-standing arguments.
+    fn split_unknown_flags(argv: Vec<OsString>) -> (Vec<OsString>, Vec<OsString>) {
+        let takes_value = |flag: &cli::Flag| flag.value.is_some();
+
+        // NOTE: the command is the first free-standing argument that is not an option value.
+        let mut command = None;
+        let mut iter = argv.iter().filter_map(|arg| arg.to_str());
+        while let Some(arg) = iter.next() {
+            if let Some(flag) = cli::GLOBAL_FLAGS.iter().find(|f| f.name == arg) {
+                if takes_value(flag) {
+                    iter.next();
+                }
+            } else if !arg.starts_with('-') {
+                command = cli::find(arg);
+                break;
+            }
+        }
+
+        let known: Vec<&cli::Flag> = cli::GLOBAL_FLAGS
+            .iter()
+            .chain(command.into_iter().flat_map(|cmd| cmd.flags))
+            .collect();
+
+        let mut args = vec![];
+        let mut unknown = vec![];
+        let mut iter = argv.into_iter();
+
+        while let Some(arg) = iter.next() {
+            let Some(name) = arg.to_str().filter(|a| a.starts_with('-')) else {
+                args.push(arg);
+                continue;
+            };
+            // NOTE: options can be passed as `--name value` or `--name=value`.
+            let (name, has_value) = name
+                .split_once('=')
+                .map_or((name, false), |(n, _)| (n, true));
+
+            match known.iter().find(|f| f.name == name) {
+                Some(flag) => {
+                    args.push(arg);
+                    if takes_value(flag) && !has_value {
+                        args.extend(iter.next());
+                    }
+                }
+                None if name == "-h" => args.push(arg),
+                None => unknown.push(arg),
+            }
+        }
+
+        (args, unknown)
+    }
+
+    pub fn parse() -> Result<ParsedArguments, UsageError> {
+        let (argv, mut unknown) = split_unknown_flags(std::env::args_os().skip(1).collect());
+        let mut args = pico_args::Arguments::from_vec(argv);
+
+        // NOTE: global options are taken first so they can precede the subcommand.
+        let global = GlobalArguments {
+            config: args
+                .opt_value_from_str("--config")
+                .map_err(|e| UsageError::new(e.to_string(), None))?,
+            overrides: args
+                .values_from_str("--set")
+                .map_err(|e| UsageError::new(e.to_string(), None))?,
+            strict: args.contains("--strict"),
+            color: args
+                .opt_value_from_str("--color")
+                .map_err(|e| UsageError::new(e.to_string(), None))?
+                .unwrap_or_default(),
+        };
+
+        let wants_help = args.contains(["-h", "--help"]);
+
+        let subcommand = args
+            .subcommand()
+            .map_err(|e| UsageError::new(e.to_string(), None))?;
+
+        let command = match subcommand.as_deref() {
+            None if wants_help => AppArguments::Help { command: None },
+            None => return Err(UsageError::new("no command specified", None)),
+            Some(name) => {
+                let spec = cli::find(name)
+                    .ok_or_else(|| UsageError::new(format!("unknown command `{name}`"), None))?;
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+                if wants_help {
+                    AppArguments::Help {
+                        command: Some(spec.name.into()),
+                    }
+                } else {
+                    parse_command(spec.name, &mut args)
+                        .map_err(|e| UsageError::from_pico(e, spec))?
+                }
+            }
+        };
+
+        if wants_help {
+            // NOTE: arguments of the described command are irrelevant when printing its help.
+            unknown.clear();
+        } else {
+            unknown.extend(args.finish());
+        }
+
+        Ok(ParsedArguments {
+            global,
+            command,
+            unknown,
+        })
+    }
+
+    fn parse_command(
+        name: &str,
+        args: &mut pico_args::Arguments,
+    ) -> Result<AppArguments, pico_args::Error> {
+        let app_args = match name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
+                inputs: args.opt_value_from_str("--inputs")?,
             },
-            Some("time") => {
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let variants = args.contains("--variants");
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    variants,
                 }
             }
-            Some("download") => AppArguments::Download {
+            "download" => AppArguments::Download {
                 day: args.free_from_str()?,
             },
-            Some("read") => AppArguments::Read {
+            "generate" => AppArguments::Generate {
                 day: args.free_from_str()?,
+                seed: args.opt_value_from_str("--seed")?,
+                size: args.opt_value_from_str("--size")?,
+                overwrite: args.contains("--overwrite"),
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            "read" => AppArguments::Read {
+                day: args.free_from_str()?,
+            },
+            "puzzles" => {
+                let part = args.opt_value_from_str("--part")?;
+                let action: String = args.free_from_str()?;
+
+                match action.as_str() {
+                    "search" => {
+                        let mut words: Vec<String> = vec![args.free_from_str()?];
+                        while let Some(word) = args.opt_free_from_str()? {
+                            words.push(word);
+                        }
+                        AppArguments::PuzzlesSearch {
+                            query: words.join(" "),
+                        }
+                    }
+                    "show" => AppArguments::PuzzlesShow {
+                        day: args.free_from_str()?,
+                        part,
+                    },
+                    _ => {
+                        return Err(pico_args::Error::ArgumentParsingFailed {
+                            cause: format!(
+                                "unknown action `{action}`, expecting `search` or `show`"
+                            ),
+                        })
+                    }
+                }
+            }
+            "scaffold" => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
+            "solve" => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                verbose: args.contains("--verbose"),
+                variant: args.opt_value_from_str("--variant")?,
+                trace: args.contains("--trace"),
+                viz: args.opt_value_from_str("--viz")?,
+                fps: args.opt_value_from_str("--fps")?,
+            },
+            "scale" => AppArguments::Scale {
+                day: args.free_from_str()?,
+                tile: args.contains("--tile"),
+                sizes: args.opt_value_from_fn("--sizes", parse_sizes)?,
+                seed: args.opt_value_from_str("--seed")?,
+            },
+            "repl" => AppArguments::Repl {
+                day: args.free_from_str()?,
+            },
+            "serve" => AppArguments::Serve {
+                port: args.opt_value_from_str("--port")?,
+            },
+            "completions" => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            "help" => AppArguments::Help {
+                command: args.opt_free_from_str()?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            "today" => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            _ => unreachable!("command `{name}` is listed in `cli::COMMANDS` but not handled"),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
-
         Ok(app_args)
     }
+
+    /// Parses a comma-separated list of sizes, e.g. `100,200,400`.
+    fn parse_sizes(list: &str) -> Result<Vec<usize>, String> {
+        list.split(',')
+            .map(|size| {
+                size.trim()
+                    .parse()
+                    .map_err(|_| format!("`{size}` is not a size"))
+            })
+            .collect()
+    }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    if let Err(err) = try_main() {
+        err.exit();
+    }
+}
+
+fn try_main() -> Result<(), Error> {
+    let ParsedArguments {
+        global,
+        command,
+        unknown,
+    } = parse().map_err(|e| Error::Usage(e.to_string()))?;
+
+    output::init(global.color, None);
+    let config = config::init(global.config.as_deref(), &global.overrides)?;
+
+    if !unknown.is_empty() {
+        let spec = command_spec(&command);
+        if global.strict || config.cli.strict {
+            let err = UsageError::unknown_arguments(&unknown, spec);
+            return Err(Error::Usage(err.to_string()));
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        eprintln!("Warning: unknown argument(s): {unknown:?}.");
+    }
+
+    run(command)
+}
+
+/// Returns the CLI description of a parsed command.
+fn command_spec(command: &AppArguments) -> Option<&'static cli::Command> {
+    let name = match command {
+        AppArguments::Download { .. } => "download",
+        AppArguments::Generate { .. } => "generate",
+        AppArguments::Read { .. } => "read",
+        AppArguments::PuzzlesSearch { .. } | AppArguments::PuzzlesShow { .. } => "puzzles",
+        AppArguments::Scaffold { .. } => "scaffold",
+        AppArguments::Solve { .. } => "solve",
+        AppArguments::All { .. } => "all",
+        AppArguments::Time { .. } => "time",
+        AppArguments::Scale { .. } => "scale",
+        AppArguments::Repl { .. } => "repl",
+        AppArguments::Serve { .. } => "serve",
+        AppArguments::Completions { .. } => "completions",
+        AppArguments::Help { .. } => "help",
+        #[cfg(feature = "today")]
+        AppArguments::Today { .. } => "today",
+    };
+    cli::find(name)
+}
+
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All { release, inputs } => all::handle(release, inputs),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            variants,
+        } => time::handle(day, all, store, variants),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Generate {
+            day,
+            seed,
+            size,
+            overwrite,
+        } => generate::handle(day, seed, size, overwrite),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::PuzzlesSearch { query } => puzzles::search(&query),
+        AppArguments::PuzzlesShow { day, part } => puzzles::show(day, part),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+        } => {
+            scaffold::handle(day, overwrite)?;
+            if download {
+                download::handle(day)?;
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
+            Ok(())
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            verbose,
+            submit,
+            variant,
+            trace,
+            viz,
+            fps,
+        } => solve::handle(
+            day,
+            release,
+            dhat,
+            verbose,
+            submit,
+            variant,
+            solve::Diagnostics { trace, viz, fps },
+        ),
+        AppArguments::Scale {
+            day,
+            tile,
+            sizes,
+            seed,
+        } => scale::handle(day, tile, sizes, seed),
+        AppArguments::Repl { day } => repl::handle(day),
+        AppArguments::Serve { port } => serve::handle(port),
+        AppArguments::Completions { shell } => completions::handle(shell),
+        AppArguments::Help { command } => match command.as_deref().map(cli::find) {
+            None => {
+                print!("{}", cli::help());
+                Ok(())
+            }
+            Some(Some(spec)) => {
+                print!("{}", spec.help());
+                Ok(())
+            }
+            Some(None) => {
+                let err = UsageError {
+                    message: format!("unknown command `{}`", command.unwrap_or_default()),
+                    command: None,
                 };
+                Err(Error::Usage(err.to_string()))
             }
         },
-    };
+        #[cfg(feature = "today")]
+        AppArguments::Today { wait } => today::handle(wait),
+    }
 }
@@ -0,0 +1,718 @@
+//! Helpers for reading puzzle inputs.
+//!
+//! Most inputs can be read with the quick helpers: [`unsigned`] and [`signed`] pull every
+//! integer out of arbitrary text, [`sections`] splits on blank lines and [`grid_with_markers`]
+//! reads a map and the positions of its special tiles. Inputs with more structure can be
+//! described with the small combinators below, which report the line and column of
+//! unexpected text instead of panicking.
+//!
+//! ```
+//! use advent_of_code::parse::{self, complete, int, literal, pair, preceded};
+//!
+//! assert_eq!(parse::signed::<i32>("p=0,4 v=3,-3").collect::<Vec<_>>(), [0, 4, 3, -3]);
+//!
+//! let button = pair(preceded(literal("X+"), int::<i64>()), preceded(literal(", Y+"), int()));
+//! assert_eq!(complete(&button, "X+94, Y+34"), Ok((94, 34)));
+//!
+//! let error = complete(&button, "X+94; Y+34").unwrap_err();
+//! assert_eq!(error.to_string(), "line 1, column 5: expected `, Y+`, found `; Y+34`");
+//! ```
+use std::{any::type_name, fmt::Display, str::FromStr};
+
+use crate::grid::{Grid, Point};
+
+/// Extracts every unsigned integer from `text`, ignoring everything else.
+///
+/// # Panics
+/// If an integer doesn't fit into `T`.
+pub fn unsigned<T: FromStr>(text: &str) -> impl Iterator<Item = T> + use<'_, T> {
+    numbers(text, false).map(parse_number)
+}
+
+/// Extracts every integer from `text`, ignoring everything else.
+///
+/// A `-` directly in front of digits is read as a sign, unless it follows a digit itself, so
+/// ranges like `3-5` are read as `3` and `5`.
+///
+/// # Panics
+/// If an integer doesn't fit into `T`.
+pub fn signed<T: FromStr>(text: &str) -> impl Iterator<Item = T> + use<'_, T> {
+    numbers(text, true).map(parse_number)
+}
+
+fn numbers(text: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+
+        let mut start = i;
+        if signed
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit())
+        {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        Some(&text[start..i])
+    })
+}
+
+fn parse_number<T: FromStr>(number: &str) -> T {
+    number
+        .parse()
+        .unwrap_or_else(|_| panic!("{number} does not fit into {}", type_name::<T>()))
+}
+
+/// Splits `input` into the blocks of lines separated by blank lines.
+///
+/// Leading, trailing and repeated blank lines are skipped and the sections don't include the
+/// newline ending their last line.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            if let Some(start) = start.take() {
+                sections.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = start {
+        sections.push(&input[start..end]);
+    }
+    sections
+}
+
+/// Parses a grid of characters and finds the position of each of `markers`, which must occur
+/// exactly once.
+///
+/// The markers stay in the grid; overwrite them if they stand on a regular tile.
+///
+/// ```
+/// use advent_of_code::parse;
+///
+/// let (grid, [start, end]) = parse::grid_with_markers("S.#\n..E", ['S', 'E']).unwrap();
+/// assert_eq!((start.x, start.y, end.x, end.y), (0, 0, 2, 1));
+/// assert_eq!(grid.width(), 3);
+/// ```
+pub fn grid_with_markers<const N: usize>(
+    input: &str,
+    markers: [char; N],
+) -> Result<(Grid<char>, [Point; N]), Error> {
+    let mut found: [Option<Point>; N] = [None; N];
+    let mut cells = vec![];
+    let mut width = None;
+    let mut offset = 0;
+
+    for (y, line) in input.split_inclusive('\n').enumerate() {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let at = |column: usize| {
+            Input::at(
+                input,
+                offset
+                    + content
+                        .char_indices()
+                        .nth(column)
+                        .map_or(content.len(), |(i, _)| i),
+            )
+        };
+
+        let mut len = 0;
+        for (x, c) in content.chars().enumerate() {
+            if let Some(i) = markers.iter().position(|m| *m == c) {
+                if found[i].is_some() {
+                    return Err(at(x).error(format!("a single `{c}`")));
+                }
+                found[i] = Some(Point::new(x as i32, y as i32));
+            }
+            cells.push(c);
+            len += 1;
+        }
+
+        match width {
+            None => width = Some(len),
+            Some(width) if width != len => {
+                return Err(at(len.min(width)).error(format!("a line of {width} characters")));
+            }
+            Some(_) => {}
+        }
+        offset += line.len();
+    }
+
+    let mut positions = [Point::ORIGIN; N];
+    for (i, position) in found.into_iter().enumerate() {
+        positions[i] = position
+            .ok_or_else(|| Input::at(input, input.len()).error(format!("a `{}`", markers[i])))?;
+    }
+
+    Ok((Grid::from_vec(width.unwrap_or(0), cells), positions))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error describing where and why parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// The 1-based line of the unexpected text.
+    pub line: usize,
+    /// The 1-based column of the unexpected text, counted in characters.
+    pub column: usize,
+    /// A description of what was expected instead.
+    pub expected: String,
+    /// The unexpected text, up to the end of its line. Long lines are cut off with `…`.
+    pub found: String,
+    offset: usize,
+    at_end: bool,
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.at_end {
+            f.write_str("end of input")
+        } else if self.found.is_empty() {
+            f.write_str("end of line")
+        } else {
+            write!(f, "`{}`", self.found)
+        }
+    }
+}
+
+/// Characters of the unexpected text an [`Error`] shows at most.
+const MAX_FOUND: usize = 40;
+
+/// The text being parsed and the position of the next character to read.
+#[derive(Debug, Clone, Copy)]
+pub struct Input<'a> {
+    text: &'a str,
+    pos: usize,
+    // NOTE: 0-based line and column of `pos`, kept up to date while reading so that creating an
+    // error doesn't need to rescan the text.
+    line: usize,
+    column: usize,
+    // NOTE: whether errors say where and why parsing failed. `find_all` discards the error of
+    // every position it tries, so it skips tracking lines and building messages.
+    detailed: bool,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: 0,
+            line: 0,
+            column: 0,
+            detailed: true,
+        }
+    }
+
+    /// Creates an input positioned at the byte offset `pos` of `text`.
+    fn at(text: &'a str, pos: usize) -> Self {
+        let mut input = Self::new(text);
+        input.take(pos);
+        input
+    }
+
+    /// The text that hasn't been read yet.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// Checks whether all of the text has been read.
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.text.len()
+    }
+
+    /// Returns the first `len` bytes of the rest and moves past them.
+    fn take(&mut self, len: usize) -> &'a str {
+        let taken = &self.rest()[..len];
+        self.pos += len;
+        if !self.detailed {
+            return taken;
+        }
+        for c in taken.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+        }
+        taken
+    }
+
+    /// Creates an error at the current position.
+    pub fn error(&self, expected: impl Display) -> Error {
+        let mut error = Error {
+            line: self.line + 1,
+            column: self.column + 1,
+            expected: String::new(),
+            found: String::new(),
+            offset: self.pos,
+            at_end: self.is_empty(),
+        };
+        if !self.detailed {
+            return error;
+        }
+
+        let mut found: String = self
+            .rest()
+            .chars()
+            .take_while(|c| *c != '\n')
+            .take(MAX_FOUND + 1)
+            .collect();
+        if found.ends_with('\r') {
+            found.pop();
+        }
+        if found.chars().count() > MAX_FOUND {
+            found.pop();
+            found.push('…');
+        }
+        error.expected = expected.to_string();
+        error.found = found;
+        error
+    }
+}
+
+/// Something that reads a `T` from the start of an [`Input`].
+///
+/// Every function `Fn(&mut Input) -> Result<T, Error>` is a parser, as are references to
+/// parsers. On failure, parsers may have consumed part of the input; combinators that try
+/// alternatives reset it themselves.
+pub trait Parser<'a, T>: Fn(&mut Input<'a>) -> Result<T, Error> {
+    fn parse(&self, input: &mut Input<'a>) -> Result<T, Error> {
+        self(input)
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F where F: Fn(&mut Input<'a>) -> Result<T, Error> {}
+
+/// Runs `parser` on all of `text`, which may only be followed by whitespace.
+pub fn complete<'a, T>(parser: impl Parser<'a, T>, text: &'a str) -> Result<T, Error> {
+    let mut input = Input::new(text);
+    let value = parser.parse(&mut input)?;
+
+    let trailing = input.rest().len() - input.rest().trim_start().len();
+    input.take(trailing);
+    if input.is_empty() {
+        Ok(value)
+    } else {
+        Err(input.error("end of input"))
+    }
+}
+
+/// Finds every non-overlapping match of `parser` in `text`, with the byte offset it starts at.
+pub fn find_all<'a, T, P: Parser<'a, T>>(
+    parser: P,
+    text: &'a str,
+) -> impl Iterator<Item = (usize, T)> + use<'a, T, P> {
+    let mut input = Input::new(text);
+    input.detailed = false;
+
+    std::iter::from_fn(move || {
+        while !input.is_empty() {
+            let start = input;
+            match parser.parse(&mut input) {
+                Ok(value) if input.pos > start.pos => return Some((start.pos, value)),
+                _ => {
+                    input = start;
+                    let next = input.rest().chars().next().map_or(1, char::len_utf8);
+                    input.take(next);
+                }
+            }
+        }
+        None
+    })
+}
+
+/// Reads exactly `expected`.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &mut Input<'a>| {
+        if input.rest().starts_with(expected) {
+            Ok(input.take(expected.len()))
+        } else {
+            Err(input.error(Quoted(expected)))
+        }
+    }
+}
+
+/// Displays a literal in backticks, escaped. Escaping only when displayed keeps failed attempts
+/// in [`find_all`] cheap.
+struct Quoted(&'static str);
+
+impl Display for Quoted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.0.escape_debug())
+    }
+}
+
+/// Reads between `min` and `max` characters matching `predicate`, as many as possible.
+pub fn take_while<'a>(
+    min: usize,
+    max: usize,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    move |input: &mut Input<'a>| {
+        let (count, len) = input
+            .rest()
+            .chars()
+            .take(max)
+            .take_while(|c| predicate(*c))
+            .fold((0, 0), |(count, len), c| (count + 1, len + c.len_utf8()));
+
+        if count < min {
+            let start = *input;
+            input.take(len);
+            return Err(start.error(format_args!("at least {min} matching characters")));
+        }
+        Ok(input.take(len))
+    }
+}
+
+/// Reads an unsigned integer.
+pub fn uint<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &mut Input<'a>| {
+        let len = input.rest().bytes().take_while(u8::is_ascii_digit).count();
+        number(input, len, "an unsigned integer")
+    }
+}
+
+/// Reads an integer with an optional `+` or `-` sign.
+pub fn int<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &mut Input<'a>| {
+        let rest = input.rest().as_bytes();
+        let sign = usize::from(matches!(rest.first(), Some(b'+' | b'-')));
+        let digits = rest[sign..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        number(
+            input,
+            if digits == 0 { 0 } else { sign + digits },
+            "an integer",
+        )
+    }
+}
+
+fn number<'a, T: FromStr>(input: &mut Input<'a>, len: usize, what: &str) -> Result<T, Error> {
+    if len == 0 {
+        return Err(input.error(what));
+    }
+    match input.rest()[..len].parse() {
+        Ok(value) => {
+            input.take(len);
+            Ok(value)
+        }
+        Err(_) => Err(input.error(format_args!(
+            "an integer that fits into {}",
+            type_name::<T>()
+        ))),
+    }
+}
+
+/// Reads a word of letters, digits and underscores.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &mut Input<'a>| {
+        let len = input
+            .rest()
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(input.rest().len());
+        if len == 0 {
+            return Err(input.error("a word"));
+        }
+        Ok(input.take(len))
+    }
+}
+
+/// Skips any spaces and tabs.
+pub fn spaces<'a>() -> impl Parser<'a, ()> {
+    move |input: &mut Input<'a>| {
+        let len = input.rest().len() - input.rest().trim_start_matches([' ', '\t']).len();
+        input.take(len);
+        Ok(())
+    }
+}
+
+/// Converts the result of `parser` with `f`.
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: &mut Input<'a>| parser.parse(input).map(&f)
+}
+
+/// Reads `first` and then `second`.
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &mut Input<'a>| Ok((first.parse(input)?, second.parse(input)?))
+}
+
+/// Reads `prefix` and then `parser`, keeping only the latter.
+pub fn preceded<'a, A, T>(
+    prefix: impl Parser<'a, A>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    move |input: &mut Input<'a>| {
+        prefix.parse(input)?;
+        parser.parse(input)
+    }
+}
+
+/// Reads `parser` and then `suffix`, keeping only the former.
+pub fn terminated<'a, T, B>(
+    parser: impl Parser<'a, T>,
+    suffix: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    move |input: &mut Input<'a>| {
+        let value = parser.parse(input)?;
+        suffix.parse(input)?;
+        Ok(value)
+    }
+}
+
+/// Reads `parser` between `open` and `close`, keeping only its result.
+pub fn delimited<'a, A, T, B>(
+    open: impl Parser<'a, A>,
+    parser: impl Parser<'a, T>,
+    close: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    terminated(preceded(open, parser), close)
+}
+
+/// Tries `first`, and `second` if it fails.
+pub fn or<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &mut Input<'a>| {
+        let start = *input;
+        let first_error = match first.parse(input) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        *input = start;
+        let second_error = match second.parse(input) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        if !input.detailed {
+            return Err(second_error);
+        }
+
+        // NOTE: report the alternative that got further, or both if they failed at once.
+        Err(match first_error.offset.cmp(&second_error.offset) {
+            std::cmp::Ordering::Greater => first_error,
+            std::cmp::Ordering::Less => second_error,
+            std::cmp::Ordering::Equal => Error {
+                expected: format!("{} or {}", first_error.expected, second_error.expected),
+                ..second_error
+            },
+        })
+    }
+}
+
+/// Reads `parser` if possible.
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &mut Input<'a>| {
+        let start = *input;
+        match parser.parse(input) {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => {
+                *input = start;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Reads `parser` as often as possible.
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &mut Input<'a>| {
+        let mut values = vec![];
+        loop {
+            let start = *input;
+            match parser.parse(input) {
+                Ok(value) if input.pos > start.pos => values.push(value),
+                _ => {
+                    *input = start;
+                    return Ok(values);
+                }
+            }
+        }
+    }
+}
+
+/// Reads one or more `parser`s separated by `separator`.
+pub fn separated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &mut Input<'a>| {
+        let mut values = vec![parser.parse(input)?];
+        loop {
+            let start = *input;
+            if separator.parse(input).is_err() {
+                *input = start;
+                return Ok(values);
+            }
+            values.push(parser.parse(input)?);
+        }
+    }
+}
+
+/// Reads `parser` once per line, until the end of the input or a blank line.
+pub fn lines<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    let newline = or(literal("\r\n"), literal("\n"));
+    move |input: &mut Input<'a>| {
+        let mut values = vec![];
+        loop {
+            let line = input.rest().lines().next().unwrap_or("");
+            if line.trim().is_empty() {
+                return Ok(values);
+            }
+            values.push(parser.parse(input)?);
+            if input.is_empty() {
+                return Ok(values);
+            }
+            newline.parse(input)?;
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_integers() {
+        let text = "p=0,4 v=3,-3 range 3-5 big 12345678901";
+        assert_eq!(
+            signed::<i64>(text).collect::<Vec<_>>(),
+            [0, 4, 3, -3, 3, 5, 12345678901]
+        );
+        assert_eq!(
+            unsigned::<u64>(text).collect::<Vec<_>>(),
+            [0, 4, 3, 3, 3, 5, 12345678901]
+        );
+        assert_eq!(signed::<i8>("-5").next(), Some(-5));
+    }
+
+    #[test]
+    #[should_panic(expected = "300 does not fit into u8")]
+    fn rejects_overflowing_integers() {
+        unsigned::<u8>("300").for_each(drop);
+    }
+
+    #[test]
+    fn splits_sections() {
+        assert_eq!(sections("a\nb\n\nc\r\n\r\n\n\nd\n"), ["a\nb", "c", "d"]);
+        assert_eq!(sections("\n\na"), ["a"]);
+        assert!(sections("\n").is_empty());
+    }
+
+    #[test]
+    fn captures_markers() {
+        let (grid, [robot]) = grid_with_markers("#..\n.@.\n", ['@']).unwrap();
+        assert_eq!(grid.to_string(), "#..\n.@.");
+        assert_eq!(robot, Point::new(1, 1));
+
+        let error = grid_with_markers("S.\nS.", ['S']).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            grid_with_markers("..\n..", ['E']).unwrap_err().expected,
+            "a `E`"
+        );
+
+        let error = grid_with_markers("...\n.\n", ['.']).unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (1, "a single `.`"));
+        let error = grid_with_markers("..\n.\n", []).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn combines_parsers() {
+        let rule = separated(uint::<u32>(), literal("|"));
+        assert_eq!(
+            complete(lines(&rule), "47|53\n97|13\n"),
+            Ok(vec![vec![47, 53], vec![97, 13]])
+        );
+
+        let equation = pair(
+            terminated(uint::<u64>(), literal(":")),
+            many(preceded(spaces(), uint::<u64>())),
+        );
+        assert_eq!(complete(&equation, "190: 10 19"), Ok((190, vec![10, 19])));
+
+        let sign = or(map(literal("+"), |_| 1), map(literal("-"), |_| -1));
+        assert_eq!(
+            complete(pair(&sign, opt(word())), "-abc"),
+            Ok((-1, Some("abc")))
+        );
+        assert_eq!(complete(&sign, "*").unwrap_err().expected, "`+` or `-`");
+    }
+
+    #[test]
+    fn reports_positions() {
+        let error = complete(lines(int::<i8>()), "1\n-2\n300").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "an integer that fits into i8");
+
+        let error = complete(lines(int::<i8>()), "1\n2 3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected `\\r\\n` or `\\n`, found ` 3`"
+        );
+
+        let long = format!("x{}\r\n", "y".repeat(100));
+        let error = complete(literal("a"), &long).unwrap_err();
+        assert_eq!(error.found, format!("x{}…", "y".repeat(39)));
+        let error = complete(literal("a"), "äb\r\n").unwrap_err();
+        assert_eq!(error.found, "äb");
+    }
+
+    #[test]
+    fn finds_matches() {
+        let mul = delimited(
+            literal("mul("),
+            pair(terminated(uint::<u32>(), literal(",")), uint::<u32>()),
+            literal(")"),
+        );
+        let found: Vec<_> = find_all(mul, "xmul(2,4)%&mul[3,7]mul(11,8)").collect();
+        assert_eq!(found, [(1, (2, 4)), (19, (11, 8))]);
+
+        let toggle = or(
+            map(literal("do()"), |_| true),
+            map(literal("don't()"), |_| false),
+        );
+        let found: Vec<_> = find_all(toggle, "don't()xdo()do(").collect();
+        assert_eq!(found, [(0, false), (8, true)]);
+
+        let digits = take_while(1, 3, |c| c.is_ascii_digit());
+        assert_eq!(complete(&digits, "123"), Ok("123"));
+        assert!(complete(&digits, "1234").is_err());
+    }
+}
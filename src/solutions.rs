@@ -0,0 +1,172 @@
+//! The solutions of all days, compiled into the library so that other code can call them.
+//!
+//! Each `src/bin/<day>.rs` is included as a module, its binary keeps working as before:
+//!
+//! ```
+//! use advent_of_code::{day, part};
+//!
+//! let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+//! let answer = advent_of_code::solve(day!(1), part!(1), input)?;
+//! assert_eq!(answer.as_str(), "11");
+//! # Ok::<(), advent_of_code::template::Error>(())
+//! ```
+use std::fmt::Display;
+
+use crate::check::quietly;
+use crate::template::inspect::{Factory, Inspect};
+use crate::template::{Day, Error, Part};
+
+/// A day that has a solution.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub day: Day,
+    /// The parts the solution solves.
+    pub parts: &'static [Part],
+    solve: fn(Part, &str) -> Option<Option<String>>,
+    inspect: Option<Factory>,
+}
+
+impl Solution {
+    /// Creates the day's inspection state for `input`, if it registered one.
+    pub fn inspect(&self, input: &str) -> Option<Box<dyn Inspect>> {
+        self.inspect.map(|inspect| inspect(input))
+    }
+}
+
+macro_rules! solutions {
+    ($( $module:ident = $path:literal ),* $(,)?) => {
+        $(
+            #[cfg(any(not(test), feature = "test_lib"))]
+            #[path = $path]
+            mod $module;
+        )*
+
+        #[cfg(any(not(test), feature = "test_lib"))]
+        const SOLUTIONS: &[Solution] = &[$(
+            Solution {
+                day: $module::__solution::DAY,
+                parts: $module::__solution::PARTS,
+                solve: $module::__solution::solve,
+                inspect: $module::__solution::INSPECT,
+            }
+        ),*];
+
+        #[cfg(not(any(not(test), feature = "test_lib")))]
+        const SOLUTIONS: &[Solution] = &[];
+    };
+}
+
+// NOTE: the solutions' tests already run with their binaries, so they are left out of the
+// library's test build unless its own tests run.
+solutions! {
+    day01 = "bin/01.rs",
+    day02 = "bin/02.rs",
+    day03 = "bin/03.rs",
+    day04 = "bin/04.rs",
+    day05 = "bin/05.rs",
+    day06 = "bin/06.rs",
+    day07 = "bin/07.rs",
+    day08 = "bin/08.rs",
+    day09 = "bin/09.rs",
+    day10 = "bin/10.rs",
+    day11 = "bin/11.rs",
+    day12 = "bin/12.rs",
+    day13 = "bin/13.rs",
+    day14 = "bin/14.rs",
+    day15 = "bin/15.rs",
+    day16 = "bin/16.rs",
+    day17 = "bin/17.rs",
+    day18 = "bin/18.rs",
+    day19 = "bin/19.rs",
+    day20 = "bin/20.rs",
+    day21 = "bin/21.rs",
+    day22 = "bin/22.rs",
+    day23 = "bin/23.rs",
+    day24 = "bin/24.rs",
+    day25 = "bin/25.rs",
+}
+
+/// The answer of a part, as it would be submitted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Answer(String);
+
+impl Answer {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Parses the answer, e.g. into a number.
+    pub fn parse<T: std::str::FromStr>(&self) -> Result<T, T::Err> {
+        self.0.parse()
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Returns the days that have a solution, in order.
+pub fn days() -> &'static [Solution] {
+    SOLUTIONS
+}
+
+/// Solves `part` of `day` for `input`.
+///
+/// Fails if the day does not have this part, or if the solution finds no answer or panics.
+pub fn solve(day: Day, part: Part, input: &str) -> Result<Answer, Error> {
+    let solution = SOLUTIONS
+        .iter()
+        .find(|solution| solution.day == day)
+        .ok_or_else(|| Error::Usage(format!("day {day} has no solution")))?;
+
+    let answer = quietly(|| (solution.solve)(part, input))
+        .map_err(|message| Error::Solution(format!("day {day} part {part} panicked: {message}")))?;
+
+    match answer {
+        Some(Some(answer)) => Ok(Answer(answer)),
+        Some(None) => Err(Error::Solution(format!(
+            "day {day} part {part} found no answer"
+        ))),
+        None => Err(Error::Usage(format!("day {day} has no part {part}"))),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{days, solve};
+    use crate::template::{all_days, read_file, Error};
+    use crate::{day, part};
+
+    #[test]
+    fn lists_every_day() {
+        let listed: Vec<_> = days().iter().map(|solution| solution.day).collect();
+        assert_eq!(listed, all_days().collect::<Vec<_>>());
+        assert_eq!(days()[0].parts, [part!(1), part!(2)]);
+    }
+
+    #[test]
+    fn solves_examples() {
+        let input = read_file("examples", day!(1));
+        assert_eq!(solve(day!(1), part!(1), &input).unwrap().as_str(), "11");
+        assert_eq!(solve(day!(1), part!(2), &input).unwrap().parse(), Ok(31));
+    }
+
+    #[test]
+    fn reports_failures() {
+        let e = solve(day!(1), part!(1), "not a list").unwrap_err();
+        assert!(
+            matches!(e, Error::Solution(ref m) if m.contains("panicked")),
+            "{e}"
+        );
+
+        let e = solve(day!(25), part!(2), "").unwrap_err();
+        assert!(
+            matches!(e, Error::Solution(ref m) if m.contains("no answer")),
+            "{e}"
+        );
+    }
+}
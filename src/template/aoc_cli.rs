@@ -1,45 +1,38 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io::{self, BufRead, BufReader, Read, Write},
     process::{Command, Output, Stdio},
+    thread,
+    time::Duration,
 };
 
-use crate::template::Day;
+use regex::Regex;
 
-#[derive(Debug)]
-pub enum AocCommandError {
-    CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
-}
+use crate::template::{config, Day, Error, Part};
 
-impl Display for AocCommandError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
-            }
-        }
-    }
-}
+const MISSING_AOC_CLI: Error = Error::MissingTool {
+    tool: "aoc",
+    hint: "Try running \"cargo install aoc-cli\" to install it.",
+};
 
-pub fn check() -> Result<(), AocCommandError> {
+pub fn check() -> Result<(), Error> {
     Command::new("aoc")
         .arg("-V")
         .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
+        .map_err(|_| MISSING_AOC_CLI)?;
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
+/// Downloads the latest puzzle description, e.g. to pick up part two once it is unlocked.
+pub fn download_puzzle(day: Day) -> Result<Output, Error> {
     let puzzle_path = get_puzzle_path(day);
 
     let args = build_args(
-        "read",
+        "download",
         &[
-            "--description-only".into(),
+            "--overwrite".into(),
+            "--puzzle-only".into(),
             "--puzzle-file".into(),
             puzzle_path,
         ],
@@ -49,7 +42,7 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
+pub fn download(day: Day) -> Result<Output, Error> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
@@ -72,27 +65,35 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+pub fn submit(day: Day, part: Part, result: &str) -> Result<SubmitOutcome, Error> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    let output = run_aoc_cli(&args)?;
+    let outcome = SubmitOutcome::parse(&output_text(&output));
+
+    if outcome == SubmitOutcome::Unknown && !output.status.success() {
+        return Err(Error::Command {
+            command: format!("aoc {}", args.join(" ")),
+            status: output.status,
+        });
+    }
+
+    Ok(outcome)
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    format!("{}/{day}.txt", config::get().paths.inputs)
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    format!("{}/{day}.md", config::get().paths.puzzles)
 }
 
 fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+    config::get().year
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -108,18 +109,245 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+fn call_aoc_cli(args: &[String]) -> Result<Output, Error> {
+    let output = run_aoc_cli(args)?;
 
     if output.status.success() {
         Ok(output)
+    } else if SubmitOutcome::parse(&output_text(&output)) == SubmitOutcome::AuthFailed {
+        Err(Error::Submission(SubmitOutcome::AuthFailed))
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(Error::Command {
+            command: format!("aoc {}", args.join(" ")),
+            status: output.status,
+        })
+    }
+}
+
+/// Runs aoc-cli, echoing its output while capturing it.
+fn run_aoc_cli(args: &[String]) -> Result<Output, Error> {
+    // println!("Calling >aoc with: {}", args.join(" "));
+    let mut cmd = Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| MISSING_AOC_CLI)?;
+
+    let broken_pipe = || io::Error::from(io::ErrorKind::BrokenPipe);
+    let stdout = cmd.stdout.take().ok_or_else(broken_pipe)?;
+    let stderr = cmd.stderr.take().ok_or_else(broken_pipe)?;
+
+    let stderr_thread = thread::spawn(move || tee(stderr, io::stderr()));
+    let stdout = tee(stdout, io::stdout());
+    let stderr = stderr_thread.join().unwrap_or_default();
+
+    Ok(Output {
+        status: cmd.wait()?,
+        stdout,
+        stderr,
+    })
+}
+
+/// Forwards `reader` line by line to `writer`, returning everything that was read.
+fn tee(reader: impl Read, mut writer: impl Write) -> Vec<u8> {
+    let mut captured = vec![];
+    let mut reader = BufReader::new(reader);
+    let mut line = vec![];
+
+    while let Ok(n) = reader.read_until(b'\n', &mut line) {
+        if n == 0 {
+            break;
+        }
+        let _ = writer.write_all(&line);
+        let _ = writer.flush();
+        captured.append(&mut line);
+    }
+
+    captured
+}
+
+fn output_text(output: &Output) -> String {
+    format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The response of Advent of Code to a submitted answer, as reported by aoc-cli.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    /// The answer was wrong. `hint` is e.g. "too high" if the response included one.
+    Incorrect {
+        hint: Option<String>,
+    },
+    /// An answer was submitted too recently. `wait` is the remaining time, if stated.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part has already been solved, or the previous part is still unsolved.
+    AlreadySolved,
+    /// The session cookie is missing, invalid or expired.
+    AuthFailed,
+    /// The output could not be interpreted.
+    Unknown,
+}
+
+impl SubmitOutcome {
+    /// Interprets the output of `aoc submit`.
+    pub fn parse(output: &str) -> Self {
+        let text = output.to_lowercase();
+
+        if text.contains("that's the right answer") {
+            return SubmitOutcome::Correct;
+        }
+
+        if text.contains("that's not the right answer") {
+            let hint = ["too high", "too low"]
+                .into_iter()
+                .find(|hint| text.contains(&format!("your answer is {hint}")))
+                .map(String::from);
+            return SubmitOutcome::Incorrect { hint };
+        }
+
+        if text.contains("you gave an answer too recently") {
+            return SubmitOutcome::RateLimited {
+                wait: parse_wait(&text),
+            };
+        }
+
+        if text.contains("you don't seem to be solving the right level") {
+            return SubmitOutcome::AlreadySolved;
+        }
+
+        let auth_patterns = [
+            "session cookie",
+            "please log in",
+            "identify yourself",
+            "unauthorized",
+        ];
+        if auth_patterns.iter().any(|p| text.contains(p)) {
+            return SubmitOutcome::AuthFailed;
+        }
+
+        SubmitOutcome::Unknown
+    }
+}
+
+/// Extracts the remaining wait time from e.g. "You have 1m 4s left to wait.".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let re = Regex::new(r"you have (?:(\d+)h\s*)?(?:(\d+)m\s*)?(?:(\d+)s)? left to wait").ok()?;
+    let caps = re.captures(text)?;
+
+    let unit = |idx: usize| -> u64 {
+        caps.get(idx)
+            .and_then(|m| m.as_str().parse().ok())
+            .unwrap_or(0)
+    };
+
+    let secs = unit(1) * 3600 + unit(2) * 60 + unit(3);
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::Incorrect { hint: Some(hint) } => {
+                write!(f, "That's not the right answer, it is {hint}.")
+            }
+            SubmitOutcome::Incorrect { hint: None } => write!(f, "That's not the right answer."),
+            SubmitOutcome::RateLimited { wait: Some(wait) } => write!(
+                f,
+                "An answer was submitted too recently, try again in {}s.",
+                wait.as_secs()
+            ),
+            SubmitOutcome::RateLimited { wait: None } => {
+                write!(f, "An answer was submitted too recently, try again later.")
+            }
+            SubmitOutcome::AlreadySolved => write!(
+                f,
+                "This part is already solved or not unlocked yet."
+            ),
+            SubmitOutcome::AuthFailed => write!(
+                f,
+                "aoc-cli could not authenticate. Refresh the session cookie in \"~/.adventofcode.session\"."
+            ),
+            SubmitOutcome::Unknown => write!(f, "Could not interpret the response of aoc-cli."),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SubmitOutcome;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_correct_answers() {
+        let output = "[INFO  aoc] 🎄 aoc-cli - Advent of Code command-line tool\nThat's the right answer! You are one gold star closer to finding the Chief Historian. [Continue to Part Two]";
+        assert_eq!(SubmitOutcome::parse(output), SubmitOutcome::Correct);
+    }
+
+    #[test]
+    fn parses_incorrect_answers() {
+        let output = "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data; please wait one minute before trying again.";
+        assert_eq!(
+            SubmitOutcome::parse(output),
+            SubmitOutcome::Incorrect {
+                hint: Some("too high".into())
+            }
+        );
+
+        let output = "That's not the right answer. If you're stuck, make sure you're using the full input data.";
+        assert_eq!(
+            SubmitOutcome::parse(output),
+            SubmitOutcome::Incorrect { hint: None }
+        );
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        let output = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 4s left to wait. [Return to Day 1]";
+        assert_eq!(
+            SubmitOutcome::parse(output),
+            SubmitOutcome::RateLimited {
+                wait: Some(Duration::from_secs(64))
+            }
+        );
+
+        let output = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 38s left to wait.";
+        assert_eq!(
+            SubmitOutcome::parse(output),
+            SubmitOutcome::RateLimited {
+                wait: Some(Duration::from_secs(38))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_solved_levels() {
+        let output = "You don't seem to be solving the right level.  Did you already complete it? [Return to Day 1]";
+        assert_eq!(SubmitOutcome::parse(output), SubmitOutcome::AlreadySolved);
+    }
+
+    #[test]
+    fn parses_auth_failures() {
+        let output = "[ERROR aoc] 🔔 Failed to read session cookie from \"/home/user/.adventofcode.session\" file";
+        assert_eq!(SubmitOutcome::parse(output), SubmitOutcome::AuthFailed);
+
+        let output = "To play, please identify yourself via one of these services:";
+        assert_eq!(SubmitOutcome::parse(output), SubmitOutcome::AuthFailed);
+    }
+
+    #[test]
+    fn parses_unknown_output() {
+        assert_eq!(SubmitOutcome::parse("¯\\_(ツ)_/¯"), SubmitOutcome::Unknown);
     }
 }
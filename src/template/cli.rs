@@ -0,0 +1,772 @@
+//! Declarative description of the template's command-line interface.
+//! Used to render help and usage texts and to generate shell completions.
+use std::{fmt::Display, str::FromStr};
+
+use crate::template::all_days;
+
+/// A command of the template CLI, e.g. `solve`.
+#[derive(Debug)]
+pub struct Command {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub args: &'static [Arg],
+    pub flags: &'static [Flag],
+}
+
+/// A positional argument of a [`Command`].
+#[derive(Debug)]
+pub struct Arg {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub required: bool,
+    pub values: Values,
+}
+
+/// A flag or option of a [`Command`].
+#[derive(Debug)]
+pub struct Flag {
+    pub name: &'static str,
+    /// Name of the value this option takes, `None` for boolean flags.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+    pub values: Values,
+}
+
+/// Values that shell completions offer for an argument.
+#[derive(Debug, Clone, Copy)]
+pub enum Values {
+    None,
+    Days,
+    File,
+    List(&'static [&'static str]),
+}
+
+const DAY_ARG: Arg = Arg {
+    name: "day",
+    help: "Day number between 1 and 25.",
+    required: true,
+    values: Values::Days,
+};
+
+const RELEASE_FLAG: Flag = Flag {
+    name: "--release",
+    value: None,
+    help: "Build the solution with optimizations.",
+    values: Values::None,
+};
+
+/// Options accepted by every command.
+pub const GLOBAL_FLAGS: &[Flag] = &[
+    Flag {
+        name: "--config",
+        value: Some("path"),
+        help: "Read the configuration from <path> instead of `aoc.toml`.",
+        values: Values::File,
+    },
+    Flag {
+        name: "--set",
+        value: Some("key=value"),
+        help: "Override a configuration value. Can be repeated.",
+        values: Values::None,
+    },
+    Flag {
+        name: "--color",
+        value: Some("when"),
+        help: "Style output: `auto` (if stdout is a terminal and `NO_COLOR` is unset), `always` or `never`.",
+        values: Values::List(&["auto", "always", "never"]),
+    },
+    Flag {
+        name: "--strict",
+        value: None,
+        help: "Treat unknown arguments as errors instead of warnings.",
+        values: Values::None,
+    },
+    Flag {
+        name: "--help",
+        value: None,
+        help: "Print help for the command.",
+        values: Values::None,
+    },
+];
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        summary: "Create the solution, input and example files for a day.",
+        args: &[DAY_ARG],
+        flags: &[
+            Flag {
+                name: "--overwrite",
+                value: None,
+                help: "Replace an existing solution file.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--download",
+                value: None,
+                help: "Download the puzzle input and description afterwards (requires aoc-cli).",
+                values: Values::None,
+            },
+        ],
+    },
+    Command {
+        name: "download",
+        summary: "Download the puzzle input and description for a day (requires aoc-cli).",
+        args: &[DAY_ARG],
+        flags: &[],
+    },
+    Command {
+        name: "generate",
+        summary: "Write a synthetic puzzle input for a day, for use without real inputs.",
+        args: &[DAY_ARG],
+        flags: &[
+            Flag {
+                name: "--seed",
+                value: Some("n"),
+                help: "Seed of the generator. The same seed and size always give the same input.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--size",
+                value: Some("n"),
+                help: "Size of the input, e.g. the number of lines. Defaults to a real input's size.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--overwrite",
+                value: None,
+                help: "Replace an existing, non-empty input file.",
+                values: Values::None,
+            },
+        ],
+    },
+    Command {
+        name: "read",
+        summary: "Download and print the puzzle description for a day (requires aoc-cli).",
+        args: &[DAY_ARG],
+        flags: &[],
+    },
+    Command {
+        name: "puzzles",
+        summary: "Search or print the downloaded puzzle descriptions.",
+        args: &[
+            Arg {
+                name: "action",
+                help: "`search` to find puzzles, `show` to print one.",
+                required: true,
+                values: Values::List(&["search", "show"]),
+            },
+            Arg {
+                name: "query",
+                help: "Words to search for, or the day to show.",
+                required: true,
+                values: Values::None,
+            },
+        ],
+        flags: &[Flag {
+            name: "--part",
+            value: Some("part"),
+            help: "With `show`, only print <part> (1 or 2).",
+            values: Values::List(&["1", "2"]),
+        }],
+    },
+    Command {
+        name: "solve",
+        summary: "Run the solution for a day against its puzzle input.",
+        args: &[DAY_ARG],
+        flags: &[
+            RELEASE_FLAG,
+            Flag {
+                name: "--dhat",
+                value: None,
+                help: "Profile heap allocations with DHAT and write `dhat-heap.json`.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--verbose",
+                value: None,
+                help: "Print results in their long form, e.g. letter art above the letters read from it.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--submit",
+                value: Some("part"),
+                help: "Submit the answer for <part> (1 or 2) via aoc-cli.",
+                values: Values::List(&["1", "2"]),
+            },
+            Flag {
+                name: "--variant",
+                value: Some("name"),
+                help: "Run this variant of the solution instead of `part_one` and `part_two`.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--trace",
+                value: None,
+                help: "Write the solution's traces to stderr, or to `trace.file`.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--viz",
+                value: Some("target"),
+                help: "Animate the solution's frames as `<file>.gif`, as PPM images in `<dir>/` or in the terminal with `term`, or save the last one as `<file>.png`.",
+                values: Values::File,
+            },
+            Flag {
+                name: "--fps",
+                value: Some("n"),
+                help: "Frames per second of the animation. Defaults to `viz.fps`.",
+                values: Values::None,
+            },
+        ],
+    },
+    Command {
+        name: "all",
+        summary: "Run the solutions for all days.",
+        args: &[],
+        flags: &[
+            RELEASE_FLAG,
+            Flag {
+                name: "--inputs",
+                value: Some("names"),
+                help: "Run against named inputs instead, `all` or a comma-separated list, and compare the results with their recorded answers.",
+                values: Values::List(&["all"]),
+            },
+        ],
+    },
+    Command {
+        name: "time",
+        summary: "Benchmark solutions. Without arguments, benches days missing from the readme.",
+        args: &[Arg {
+            name: "day",
+            help: "Only bench this day.",
+            required: false,
+            values: Values::Days,
+        }],
+        flags: &[
+            Flag {
+                name: "--all",
+                value: None,
+                help: "Bench all days, including ones that have stored timings.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--store",
+                value: None,
+                help: "Write the timings to the timings file and readme.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--variants",
+                value: None,
+                help: "Bench every variant of the solutions side by side and check that their answers agree.",
+                values: Values::None,
+            },
+        ],
+    },
+    Command {
+        name: "scale",
+        summary: "Bench a day on growing inputs and estimate how its run time scales.",
+        args: &[DAY_ARG],
+        flags: &[
+            Flag {
+                name: "--tile",
+                value: None,
+                help: "Repeat the real input instead of generating inputs. Sizes count copies.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--sizes",
+                value: Some("list"),
+                help: "Comma-separated input sizes, e.g. `100,200,400`. Defaults to fractions of a real input.",
+                values: Values::None,
+            },
+            Flag {
+                name: "--seed",
+                value: Some("n"),
+                help: "Seed of the input generator.",
+                values: Values::None,
+            },
+        ],
+    },
+    Command {
+        name: "today",
+        summary: "Scaffold, download and read the current day (requires the `today` feature).",
+        args: &[],
+        flags: &[Flag {
+            name: "--wait",
+            value: None,
+            help: "Wait for the next puzzle to unlock, unless today's isn't scaffolded yet, then download it as soon as it is available.",
+            values: Values::None,
+        }],
+    },
+    Command {
+        name: "repl",
+        summary: "Explore a day interactively: re-run its parts on other inputs and inspect its state.",
+        args: &[DAY_ARG],
+        flags: &[],
+    },
+    Command {
+        name: "serve",
+        summary: "Serve the solutions over HTTP on localhost, e.g. for a dashboard.",
+        args: &[],
+        flags: &[Flag {
+            name: "--port",
+            value: Some("n"),
+            help: "Port to listen on. Defaults to 8080.",
+            values: Values::None,
+        }],
+    },
+    Command {
+        name: "completions",
+        summary: "Print a shell completion script.",
+        args: &[Arg {
+            name: "shell",
+            help: "One of `bash`, `zsh` or `fish`.",
+            required: true,
+            values: Values::List(&["bash", "zsh", "fish"]),
+        }],
+        flags: &[],
+    },
+    Command {
+        name: "help",
+        summary: "Print help for a command.",
+        args: &[Arg {
+            name: "command",
+            help: "The command to describe.",
+            required: false,
+            values: Values::None,
+        }],
+        flags: &[],
+    },
+];
+
+/// Name of the binary that completions are generated for.
+pub const BIN_NAME: &str = "advent_of_code";
+
+/// Returns the commands available in this build.
+pub fn commands() -> impl Iterator<Item = &'static Command> {
+    COMMANDS
+        .iter()
+        .filter(|cmd| cfg!(feature = "today") || cmd.name != "today")
+}
+
+/// Looks up an available command by name.
+pub fn find(name: &str) -> Option<&'static Command> {
+    commands().find(|cmd| cmd.name == name)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Command {
+    /// One-line usage, e.g. `solve <day> [--release] [--dhat] [--submit <part>]`.
+    pub fn usage(&self) -> String {
+        let mut parts = vec![self.name.to_string()];
+
+        for arg in self.args {
+            if arg.required {
+                parts.push(format!("<{}>", arg.name));
+            } else {
+                parts.push(format!("[{}]", arg.name));
+            }
+        }
+
+        for flag in self.flags {
+            parts.push(format!("[{}]", flag.signature()));
+        }
+
+        parts.join(" ")
+    }
+
+    /// Full help text describing every argument and flag.
+    pub fn help(&self) -> String {
+        let mut out = format!("{}\n\nUsage: {BIN_NAME} {}\n", self.summary, self.usage());
+
+        if !self.args.is_empty() {
+            out.push_str("\nArguments:\n");
+            let rows: Vec<_> = self
+                .args
+                .iter()
+                .map(|arg| (format!("<{}>", arg.name), arg.help))
+                .collect();
+            out.push_str(&format_rows(&rows));
+        }
+
+        if !self.flags.is_empty() {
+            out.push_str("\nOptions:\n");
+            out.push_str(&format_flags(self.flags));
+        }
+
+        out.push_str("\nGlobal options:\n");
+        out.push_str(&format_flags(GLOBAL_FLAGS));
+        out
+    }
+}
+
+impl Flag {
+    fn signature(&self) -> String {
+        match self.value {
+            Some(value) => format!("{} <{value}>", self.name),
+            None => self.name.to_string(),
+        }
+    }
+}
+
+/// Help text listing all commands.
+pub fn help() -> String {
+    let mut out = format!(
+        "Solutions for Advent of Code in Rust.\n\nUsage: {BIN_NAME} [options] <command> [args]\n\nCommands:\n"
+    );
+
+    let rows: Vec<_> = commands()
+        .map(|cmd| (cmd.name.into(), cmd.summary))
+        .collect();
+    out.push_str(&format_rows(&rows));
+
+    out.push_str("\nGlobal options:\n");
+    out.push_str(&format_flags(GLOBAL_FLAGS));
+    out.push_str(&format!(
+        "\nRun `{BIN_NAME} help <command>` for more information on a command.\n"
+    ));
+    out
+}
+
+fn format_flags(flags: &[Flag]) -> String {
+    let rows: Vec<_> = flags.iter().map(|f| (f.signature(), f.help)).collect();
+    format_rows(&rows)
+}
+
+fn format_rows(rows: &[(String, &str)]) -> String {
+    let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(name, help)| format!("  {name:width$}  {help}\n"))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A shell supported by [`completions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = ShellFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(ShellFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Shell`].
+#[derive(Debug)]
+pub struct ShellFromStrError;
+
+impl std::error::Error for ShellFromStrError {}
+
+impl Display for ShellFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `bash`, `zsh` or `fish`")
+    }
+}
+
+/// Generates a completion script for `shell`.
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+fn day_values() -> Vec<String> {
+    all_days().map(|day| day.into_inner().to_string()).collect()
+}
+
+fn values_words(values: Values) -> Option<String> {
+    match values {
+        Values::Days => Some(day_values().join(" ")),
+        Values::List(list) => Some(list.join(" ")),
+        Values::None | Values::File => None,
+    }
+}
+
+fn bash_completions() -> String {
+    let fn_name = format!("_{BIN_NAME}");
+    let command_names: Vec<_> = commands().map(|cmd| cmd.name).collect();
+    let global_flags: Vec<_> = GLOBAL_FLAGS.iter().map(|f| f.name).collect();
+
+    let mut value_cases = String::new();
+    for flag in GLOBAL_FLAGS
+        .iter()
+        .chain(commands().flat_map(|cmd| cmd.flags))
+    {
+        if flag.value.is_none() || value_cases.contains(&format!("        {})", flag.name)) {
+            continue;
+        }
+        let reply = match flag.values {
+            Values::File => "COMPREPLY=( $(compgen -f -- \"$cur\") )".to_string(),
+            values => match values_words(values) {
+                Some(words) => format!("COMPREPLY=( $(compgen -W \"{words}\" -- \"$cur\") )"),
+                None => "COMPREPLY=()".to_string(),
+            },
+        };
+        value_cases.push_str(&format!(
+            "        {})\n            {reply}\n            return ;;\n",
+            flag.name
+        ));
+    }
+
+    let mut command_cases = String::new();
+    for cmd in commands() {
+        let mut words: Vec<String> = cmd.flags.iter().map(|f| f.name.to_string()).collect();
+        words.extend(global_flags.iter().map(|f| f.to_string()));
+        if let Some(arg) = cmd.args.first() {
+            if let Some(values) = values_words(arg.values) {
+                words.push(values);
+            }
+        }
+        if cmd.name == "help" {
+            words.extend(command_names.iter().map(|c| c.to_string()));
+        }
+        command_cases.push_str(&format!(
+            "        {})\n            COMPREPLY=( $(compgen -W \"{}\" -- \"$cur\") ) ;;\n",
+            cmd.name,
+            words.join(" ")
+        ));
+    }
+
+    format!(
+        r#"# bash completion for {BIN_NAME}
+{fn_name}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local cmd="" i
+
+    case "$prev" in
+{value_cases}    esac
+
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${{COMP_WORDS[i]}}" in
+            --config|--set|--color) ((i++)) ;;
+            -*) ;;
+            *) cmd="${{COMP_WORDS[i]}}"; break ;;
+        esac
+    done
+
+    case "$cmd" in
+        "")
+            COMPREPLY=( $(compgen -W "{commands} {globals}" -- "$cur") ) ;;
+{command_cases}    esac
+}}
+complete -F {fn_name} {BIN_NAME}
+"#,
+        commands = command_names.join(" "),
+        globals = global_flags.join(" "),
+    )
+}
+
+fn zsh_flag_spec(flag: &Flag) -> String {
+    let help = flag
+        .help
+        .replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]");
+    match flag.value {
+        None => format!("'{}[{help}]'", flag.name),
+        Some(value) => {
+            let action = match flag.values {
+                Values::File => "_files".to_string(),
+                values => values_words(values)
+                    .map(|words| format!("({words})"))
+                    .unwrap_or_default(),
+            };
+            format!("'*{}[{help}]:{value}:{action}'", flag.name)
+        }
+    }
+}
+
+fn zsh_completions() -> String {
+    let globals: Vec<_> = GLOBAL_FLAGS.iter().map(zsh_flag_spec).collect();
+
+    let command_list: String = commands()
+        .map(|cmd| {
+            format!(
+                "        '{}:{}'\n",
+                cmd.name,
+                cmd.summary.replace('\'', "'\\''").replace(':', "\\:")
+            )
+        })
+        .collect();
+
+    let mut command_cases = String::new();
+    for cmd in commands() {
+        let mut specs: Vec<String> = cmd.flags.iter().map(zsh_flag_spec).collect();
+        specs.extend(globals.iter().cloned());
+        for arg in cmd.args {
+            let action = if cmd.name == "help" {
+                format!(
+                    "({})",
+                    commands().map(|c| c.name).collect::<Vec<_>>().join(" ")
+                )
+            } else {
+                values_words(arg.values)
+                    .map(|words| format!("({words})"))
+                    .unwrap_or_default()
+            };
+            let optional = if arg.required { "" } else { ":" };
+            specs.push(format!("'{optional}:{}:{action}'", arg.name));
+        }
+        command_cases.push_str(&format!(
+            "                {})\n                    _arguments \\\n                        {} ;;\n",
+            cmd.name,
+            specs.join(" \\\n                        ")
+        ));
+    }
+
+    format!(
+        r#"#compdef {BIN_NAME}
+
+_{BIN_NAME}() {{
+    local -a commands
+    commands=(
+{command_list}    )
+
+    _arguments -C \
+        {globals} \
+        '1:command:->command' \
+        '*::arg:->args'
+
+    case $state in
+        command)
+            _describe 'command' commands ;;
+        args)
+            case $words[1] in
+{command_cases}            esac ;;
+    esac
+}}
+
+_{BIN_NAME} "$@"
+"#,
+        globals = globals.join(" \\\n        "),
+    )
+}
+
+fn fish_flag_line(condition: &str, flag: &Flag) -> String {
+    let mut line = format!(
+        "complete -c {BIN_NAME} -n '{condition}' -l {}",
+        flag.name.trim_start_matches("--")
+    );
+    if flag.value.is_some() {
+        match flag.values {
+            Values::File => line.push_str(" -r -F"),
+            values => {
+                line.push_str(" -r -f");
+                if let Some(words) = values_words(values) {
+                    line.push_str(&format!(" -a '{words}'"));
+                }
+            }
+        }
+    }
+    line.push_str(&format!(" -d '{}'\n", flag.help.replace('\'', "\\'")));
+    line
+}
+
+fn fish_completions() -> String {
+    let names: Vec<_> = commands().map(|cmd| cmd.name).collect();
+    let mut out = format!("# fish completion for {BIN_NAME}\ncomplete -c {BIN_NAME} -f\n");
+
+    for flag in GLOBAL_FLAGS {
+        out.push_str(&fish_flag_line("true", flag));
+    }
+
+    for cmd in commands() {
+        out.push_str(&format!(
+            "complete -c {BIN_NAME} -n '__fish_use_subcommand' -a {} -d '{}'\n",
+            cmd.name,
+            cmd.summary.replace('\'', "\\'")
+        ));
+
+        let condition = format!("__fish_seen_subcommand_from {}", cmd.name);
+        for flag in cmd.flags {
+            out.push_str(&fish_flag_line(&condition, flag));
+        }
+
+        let words = if cmd.name == "help" {
+            Some(names.join(" "))
+        } else {
+            cmd.args.first().and_then(|arg| values_words(arg.values))
+        };
+        if let Some(words) = words {
+            out.push_str(&format!(
+                "complete -c {BIN_NAME} -n '{condition}' -a '{words}'\n"
+            ));
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{completions, find, help, Shell};
+
+    #[test]
+    fn documents_every_flag() {
+        let solve = find("solve").unwrap();
+        assert_eq!(
+            solve.usage(),
+            "solve <day> [--release] [--dhat] [--verbose] [--submit <part>] [--variant <name>] [--trace] [--viz <target>] [--fps <n>]"
+        );
+
+        let text = solve.help();
+        for flag in ["--release", "--dhat", "--submit <part>", "--config <path>"] {
+            assert!(text.contains(flag), "missing {flag} in help");
+        }
+
+        let text = help();
+        for cmd in [
+            "scaffold",
+            "download",
+            "generate",
+            "read",
+            "solve",
+            "all",
+            "time",
+            "scale",
+            "repl",
+            "serve",
+            "completions",
+        ] {
+            assert!(text.contains(cmd), "missing {cmd} in help");
+        }
+    }
+
+    #[test]
+    fn parses_shells() {
+        assert_eq!("bash".parse::<Shell>().unwrap(), Shell::Bash);
+        assert_eq!("fish".parse::<Shell>().unwrap(), Shell::Fish);
+        assert!("pwsh".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn completes_days_and_flags() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completions(shell);
+            assert!(script.contains("1 2 3"), "{shell:?} is missing days");
+            assert!(script.contains("25"), "{shell:?} is missing days");
+            assert!(script.contains("overwrite"), "{shell:?} is missing flags");
+            assert!(script.contains("advent_of_code"));
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Abstraction over the system clock, so time-based commands can be tested without waiting.
+use std::{thread, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+pub trait Clock {
+    /// Returns the current instant.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Blocks for `duration`.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
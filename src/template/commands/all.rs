@@ -1,5 +1,170 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{collections::HashSet, path::Path};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::named_inputs::{self, Answers};
+use crate::template::run_multi::{child_commands, get_path_for_bin, run_multi};
+use crate::template::{all_days, all_parts, config, output, Day, Error};
+
+pub fn handle(is_release: bool, inputs: Option<String>) -> Result<(), Error> {
+    match inputs {
+        Some(inputs) => run_named_inputs(is_release, &inputs),
+        None => run_multi(&all_days().collect(), is_release, false)?.check(),
+    }
+}
+
+/// Runs each day against its named inputs, `all` or a comma-separated list of names, and prints
+/// the results as a matrix. Fails if a result disagrees with its recorded answer.
+fn run_named_inputs(is_release: bool, selection: &str) -> Result<(), Error> {
+    let names: Option<HashSet<&str>> =
+        (selection != "all").then(|| selection.split(',').map(str::trim).collect());
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut mismatches: Vec<String> = vec![];
+    let mut failed: Vec<String> = vec![];
+    let mut need_space = false;
+
+    for day in all_days() {
+        // NOTE: days that have not been scaffolded yet are skipped, like in a regular run.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            continue;
+        }
+
+        let inputs: Vec<_> = named_inputs::list(day)?
+            .into_iter()
+            .filter(|input| {
+                names
+                    .as_ref()
+                    .is_none_or(|n| n.contains(input.name.as_str()))
+            })
+            .collect();
+        if inputs.is_empty() {
+            continue;
+        }
+
+        let answers = Answers::read(day)?;
+        let mut columns: Vec<(String, Vec<String>)> = vec![];
+
+        for input in inputs {
+            seen.insert(input.name.clone());
+
+            let args = ["--input".to_string(), input.path.display().to_string()];
+            let results = match child_commands::capture_solution(day, is_release, &args) {
+                Ok(output) => child_commands::parse_answers(&output),
+                Err(e @ (Error::Solution(_) | Error::Child { .. })) => {
+                    eprintln!("{e}");
+                    failed.push(format!("day {day} on \"{}\"", input.name));
+                    columns.push((input.name, all_parts().map(|_| "error".into()).collect()));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let cells = all_parts()
+                .map(|part| {
+                    let result = results.iter().find(|(p, _)| *p == part).map(|(_, r)| r);
+                    let expected = answers.get(&input.name, part);
+                    let shown = |result: &Option<String>| match result {
+                        Some(result) => result.replace('\n', "⏎"),
+                        None => "✖".into(),
+                    };
+
+                    match (result, expected) {
+                        (None, _) => "-".into(),
+                        (Some(result), None) => format!("? {}", shown(result)),
+                        (Some(result), Some(expected)) if result.as_deref() == Some(expected) => {
+                            format!("✓ {}", shown(result))
+                        }
+                        (Some(result), Some(expected)) => {
+                            mismatches.push(format!(
+                                "day {day} part {part} on \"{}\": got {}, expected {expected}",
+                                input.name,
+                                shown(result)
+                            ));
+                            format!("✗ {}", shown(result))
+                        }
+                    }
+                })
+                .collect();
+            columns.push((input.name, cells));
+        }
+
+        if need_space {
+            println!();
+        }
+        need_space = true;
+        print_matrix(day, &columns);
+    }
+
+    if seen.is_empty() {
+        let inputs = config::get().paths.folder("inputs");
+        return Err(Error::Usage(format!(
+            "no named inputs found, add them as \"{}/<day>/<name>.txt\"",
+            inputs.display()
+        )));
+    }
+    if let Some(names) = names {
+        for name in names.iter().filter(|name| !seen.contains(**name)) {
+            eprintln!("Warning: no day has an input named \"{name}\".");
+        }
+    }
+
+    println!("\n✓ matches the recorded answer, ✗ disagrees, ? has no recorded answer.");
+
+    if !mismatches.is_empty() {
+        println!(
+            "\n{}{} result(s) disagree with the recorded answers:{}",
+            output::bold(),
+            mismatches.len(),
+            output::reset()
+        );
+        for mismatch in &mismatches {
+            println!("  {mismatch}");
+        }
+    }
+
+    match (mismatches.len(), failed.len()) {
+        (0, 0) => Ok(()),
+        (0, _) => Err(Error::Solution(format!(
+            "the solutions failed for {} input(s): {}",
+            failed.len(),
+            failed.join(", ")
+        ))),
+        (n, _) => Err(Error::Solution(format!(
+            "{n} result(s) disagree with the recorded answers"
+        ))),
+    }
+}
+
+/// Prints the results of a day, one column per input and one row per part.
+fn print_matrix(day: Day, columns: &[(String, Vec<String>)]) {
+    let (bold, reset) = (output::bold(), output::reset());
+    println!("{bold}Day {day}{reset}");
+    println!("------");
+
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|(name, cells)| {
+            cells
+                .iter()
+                .chain([name])
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let header: Vec<String> = columns
+        .iter()
+        .zip(&widths)
+        .map(|((name, _), width)| format!("{name:<width$}"))
+        .collect();
+    println!("{:<8}{}", "", header.join("  ").trim_end());
+
+    for (i, part) in all_parts().enumerate() {
+        let row: Vec<String> = columns
+            .iter()
+            .zip(&widths)
+            .map(|((_, cells), width)| format!("{:<width$}", cells[i]))
+            .collect();
+        println!("{:<8}{}", format!("Part {part}"), row.join("  ").trim_end());
+    }
 }
@@ -0,0 +1,7 @@
+use crate::template::cli::{self, Shell};
+use crate::template::Error;
+
+pub fn handle(shell: Shell) -> Result<(), Error> {
+    print!("{}", cli::completions(shell));
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use std::{fs, path::Path};
+
+use crate::generate::{self, DEFAULT_SEED};
+use crate::template::{config, Day, Error, ErrorContext};
+
+pub fn handle(
+    day: Day,
+    seed: Option<u64>,
+    size: Option<usize>,
+    overwrite: bool,
+) -> Result<(), Error> {
+    let generator = generate::generator(day);
+    let size = size.unwrap_or(generator.default_size);
+    if size < generator.min_size {
+        return Err(Error::Usage(format!(
+            "day {day} needs a size of at least {} {}",
+            generator.min_size, generator.unit
+        )));
+    }
+
+    let dir = &config::get().paths.inputs;
+    let path = format!("{dir}/{day}.txt");
+    // NOTE: `scaffold` creates empty input files, only real contents are worth protecting.
+    let has_input = fs::metadata(&path).is_ok_and(|meta| meta.len() > 0);
+    if has_input && !overwrite {
+        return Err(Error::Usage(format!(
+            "\"{path}\" already contains an input, pass `--overwrite` to replace it"
+        )));
+    }
+
+    let seed = seed.unwrap_or(DEFAULT_SEED);
+    let input = generate::input(day, seed, Some(size));
+    fs::create_dir_all(Path::new(dir)).context("Failed to create the inputs directory")?;
+    fs::write(&path, input + "\n").context("Failed to write input file")?;
+
+    println!(
+        "Generated \"{path}\" with {size} {} (seed {seed}).",
+        generator.unit
+    );
+    Ok(())
+}
@@ -1,6 +1,14 @@
 pub mod all;
+pub mod completions;
 pub mod download;
+pub mod generate;
+pub mod puzzles;
 pub mod read;
+pub mod repl;
 pub mod scaffold;
+pub mod scale;
+pub mod serve;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
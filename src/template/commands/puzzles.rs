@@ -0,0 +1,79 @@
+use crate::template::puzzles::{self, Snippet};
+use crate::template::{markdown, output, Day, Error, Part};
+
+/// Maximum number of matches printed by `search`.
+const MAX_RESULTS: usize = 10;
+
+pub fn search(query: &str) -> Result<(), Error> {
+    let library = puzzles::load_all()?;
+    if library.is_empty() {
+        println!("No puzzles downloaded yet. Use `cargo download <day>` to fetch them.");
+        return Ok(());
+    }
+
+    let hits = puzzles::search(&library, query);
+    if hits.is_empty() {
+        println!("No matches for \"{query}\" in {} puzzles.", library.len());
+        return Ok(());
+    }
+
+    for hit in hits.iter().take(MAX_RESULTS) {
+        println!(
+            "{}Day {} part {}{}: {} (score {})",
+            output::bold(),
+            hit.day,
+            hit.part,
+            output::reset(),
+            hit.title,
+            hit.score
+        );
+        for snippet in &hit.snippets {
+            println!("    {}", highlight(snippet));
+        }
+        println!();
+    }
+
+    if hits.len() > MAX_RESULTS {
+        println!("…and {} more.", hits.len() - MAX_RESULTS);
+    }
+    Ok(())
+}
+
+pub fn show(day: Day, part: Option<Part>) -> Result<(), Error> {
+    let puzzle = puzzles::load(day)?;
+
+    let options = markdown::Options::terminal();
+
+    let rendered = match part {
+        None => puzzle.render(&options),
+        Some(part) => {
+            let markdown = puzzle.part(part).ok_or_else(|| {
+                Error::Data(format!(
+                    "Part {part} of day {day} is not in the downloaded description. \
+                    Solve the previous part and run `cargo download {day}` again."
+                ))
+            })?;
+            markdown::render(markdown, &options)
+        }
+    };
+
+    print!("{rendered}");
+    Ok(())
+}
+
+fn highlight(snippet: &Snippet) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    for range in &snippet.matches {
+        if range.start < last {
+            continue;
+        }
+        out.push_str(&snippet.text[last..range.start]);
+        out.push_str(output::bold());
+        out.push_str(&snippet.text[range.clone()]);
+        out.push_str(output::reset());
+        last = range.end;
+    }
+    out.push_str(&snippet.text[last..]);
+    out
+}
@@ -1,15 +1,24 @@
-use std::process;
+use crate::template::{aoc_cli, markdown, puzzles, Day, Error, ErrorContext};
 
-use crate::template::{aoc_cli, Day};
+pub fn handle(day: Day) -> Result<(), Error> {
+    let refreshed = aoc_cli::check()
+        .and_then(|()| aoc_cli::download_puzzle(day).context("failed to call aoc-cli"));
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    // NOTE: a previously downloaded description is still worth showing when offline.
+    let puzzle = match (refreshed, puzzles::load(day)) {
+        (Ok(_), Ok(puzzle)) => puzzle,
+        (Err(err), Ok(puzzle)) => {
+            eprintln!("Warning: could not refresh the description: {err}");
+            eprintln!("Showing the previously downloaded description.");
+            puzzle
+        }
+        (Err(err), Err(_)) | (Ok(_), Err(err)) => return Err(err),
     };
+
+    let options = markdown::Options::terminal();
+    if options.ansi {
+        println!();
+    }
+    print!("{}", puzzle.render(&options));
+    Ok(())
 }
@@ -0,0 +1,313 @@
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+    time::Instant,
+};
+
+use crate::check::quietly;
+use crate::template::inspect::Inspect;
+use crate::template::{config, input, output, Day, Error, ErrorContext, Part};
+use crate::Solution;
+
+/// Line that ends a pasted input.
+const END_OF_PASTE: &str = ".";
+
+/// The commands of every day, as usage and help.
+const COMMANDS: &[(&str, &str)] = &[
+    ("1, 2", "Solve a part for the current input."),
+    ("run", "Solve every part."),
+    ("input", "Show where the current input comes from."),
+    (
+        "example [n]",
+        "Switch to the example, or to the one of part n.",
+    ),
+    ("load <path>", "Switch to the input in a file."),
+    (
+        "paste",
+        "Switch to an input pasted in, ended by a line holding only `.`.",
+    ),
+    ("help", "Print this help."),
+    ("quit", "Leave the REPL."),
+];
+
+pub fn handle(day: Day) -> Result<(), Error> {
+    let solution = crate::days()
+        .iter()
+        .find(|solution| solution.day == day)
+        .ok_or_else(|| Error::Usage(format!("day {day} has no solution")))?;
+
+    let mut session = Session::new(*solution, String::new(), "no input".into());
+    // NOTE: the puzzle input may not have been downloaded yet, the example is the next best thing.
+    let loaded = session
+        .load(&input_path("inputs", day, None))
+        .or_else(|_| session.load(&input_path("examples", day, None)));
+    match loaded {
+        Ok(loaded) => println!("{loaded}"),
+        Err(e) => eprintln!("{e}"),
+    }
+    println!("Type `help` for a list of commands.");
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("{}day {day}>{} ", output::bold(), output::reset());
+        io::stdout().flush().context("could not write the prompt")?;
+
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        let line = line.context("could not read the command")?;
+
+        let reply = if line.trim() == "paste" {
+            println!("Paste the input, then end it with a line holding only `{END_OF_PASTE}`.");
+            let mut text = String::new();
+            for line in lines.by_ref() {
+                let line = line.context("could not read the input")?;
+                if line.trim_end() == END_OF_PASTE {
+                    break;
+                }
+                text.push_str(&line);
+                text.push('\n');
+            }
+            let text = input::normalize(&text, &config::get().input).0.into_owned();
+            session.set_input(text, "pasted input".into());
+            Reply::Text(session.describe_input())
+        } else {
+            session.execute(&line)
+        };
+
+        match reply {
+            Reply::Text(text) if text.is_empty() => {}
+            Reply::Text(text) => println!("{text}"),
+            Reply::Error(message) => eprintln!("{message}"),
+            Reply::Quit => return Ok(()),
+        }
+    }
+}
+
+/// Returns the path of the input or example of `day`, e.g. `data/examples/01-2.txt` for `n = 2`.
+fn input_path(folder: &str, day: Day, n: Option<&str>) -> std::path::PathBuf {
+    let name = match n {
+        Some(n) => format!("{day}-{n}.txt"),
+        None => format!("{day}.txt"),
+    };
+    config::get().paths.folder(folder).join(name)
+}
+
+/// Outcome of a command.
+#[derive(Debug, PartialEq, Eq)]
+enum Reply {
+    Text(String),
+    Error(String),
+    Quit,
+}
+
+/// A day explored in the REPL: its current input and, once used, its inspection state.
+struct Session {
+    solution: Solution,
+    input: String,
+    /// Where the input came from, e.g. its path.
+    source: String,
+    inspector: Option<Box<dyn Inspect>>,
+}
+
+impl Session {
+    fn new(solution: Solution, input: String, source: String) -> Self {
+        Self {
+            solution,
+            input,
+            source,
+            inspector: None,
+        }
+    }
+
+    fn set_input(&mut self, input: String, source: String) {
+        self.input = input;
+        self.source = source;
+        // NOTE: the inspection state belongs to the previous input.
+        self.inspector = None;
+    }
+
+    /// Switches to the input at `path`.
+    fn load(&mut self, path: &Path) -> Result<String, String> {
+        let input =
+            input::read(path).map_err(|e| format!("could not read \"{}\": {e}", path.display()))?;
+        self.set_input(input, format!("\"{}\"", path.display()));
+        Ok(self.describe_input())
+    }
+
+    fn describe_input(&self) -> String {
+        format!(
+            "Input: {}, {} lines",
+            self.source,
+            self.input.lines().count()
+        )
+    }
+
+    fn execute(&mut self, line: &str) -> Reply {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = words.split_first() else {
+            return Reply::Text(String::new());
+        };
+
+        let result = match (command, args) {
+            ("quit" | "exit", []) => return Reply::Quit,
+            ("help", []) => Ok(self.help()),
+            ("run", []) => Ok(self.solve(self.solution.parts)),
+            ("input", []) => Ok(self.describe_input()),
+            ("load", [path]) => self.load(Path::new(path)),
+            ("example", [] | [_]) => self.load(&input_path(
+                "examples",
+                self.solution.day,
+                args.first().copied(),
+            )),
+            _ => match command.parse::<Part>() {
+                Ok(part) if args.is_empty() => Ok(self.solve(&[part])),
+                _ => self.inspect(command, args),
+            },
+        };
+
+        match result {
+            Ok(text) => Reply::Text(text),
+            Err(message) => Reply::Error(message),
+        }
+    }
+
+    /// Solves `parts` for the current input and reports their answers and run times.
+    fn solve(&self, parts: &[Part]) -> String {
+        let results: Vec<String> = parts
+            .iter()
+            .map(|&part| {
+                let timer = Instant::now();
+                match crate::solve(self.solution.day, part, &self.input) {
+                    Ok(answer) => format!("Part {part}: {answer} ({:.1?})", timer.elapsed()),
+                    Err(e) => format!("Part {part}: ✖ {e}"),
+                }
+            })
+            .collect();
+        results.join("\n")
+    }
+
+    /// Runs an inspection command of the day, creating its state first if needed.
+    fn inspect(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        let unknown = || format!("unknown command `{command}`, try `help`");
+
+        let Some(inspector) = self.inspector()? else {
+            return Err(unknown());
+        };
+        let known = inspector
+            .commands()
+            .iter()
+            .any(|(usage, _)| usage.split_whitespace().next() == Some(command));
+        if !known {
+            return Err(unknown());
+        }
+
+        match quietly(|| inspector.run(command, args)) {
+            Ok(result) => result,
+            Err(message) => {
+                // NOTE: the state may be inconsistent after a panic.
+                self.inspector = None;
+                Err(format!(
+                    "`{command}` panicked: {message}\nThe inspection starts over."
+                ))
+            }
+        }
+    }
+
+    /// Returns the day's inspection state, `None` if it has none.
+    fn inspector(&mut self) -> Result<Option<&mut Box<dyn Inspect>>, String> {
+        if self.inspector.is_none() {
+            self.inspector = quietly(|| self.solution.inspect(&self.input))
+                .map_err(|message| format!("could not inspect the input: {message}"))?;
+        }
+        Ok(self.inspector.as_mut())
+    }
+
+    fn help(&mut self) -> String {
+        let (inspection, note) = match self.inspector() {
+            Ok(inspector) => (inspector.map_or(&[][..], |i| i.commands()), None),
+            Err(message) => (&[][..], Some(message)),
+        };
+
+        let width = COMMANDS
+            .iter()
+            .chain(inspection)
+            .map(|(usage, _)| usage.len())
+            .max()
+            .unwrap_or_default();
+        let table = |rows: &[(&str, &str)]| {
+            rows.iter()
+                .map(|(usage, help)| format!("  {usage:<width$}  {help}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let mut text = format!("Commands:\n{}", table(COMMANDS));
+        if !inspection.is_empty() {
+            text.push_str(&format!(
+                "\n\nDay {}:\n{}",
+                self.solution.day,
+                table(inspection)
+            ));
+        }
+        if let Some(note) = note {
+            text.push_str(&format!("\n\n{note}"));
+        }
+        text
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Reply, Session};
+    use crate::template::read_file;
+    use crate::{day, days, Solution};
+
+    fn start(day: crate::template::Day) -> Session {
+        let solution: Solution = *days().iter().find(|s| s.day == day).unwrap();
+        Session::new(solution, read_file("examples", day), "example".into())
+    }
+
+    fn text(reply: Reply) -> String {
+        match reply {
+            Reply::Text(text) => text,
+            reply => panic!("expected text, got {reply:?}"),
+        }
+    }
+
+    #[test]
+    fn solves_parts() {
+        let mut session = start(day!(1));
+        assert!(text(session.execute("1")).starts_with("Part 1: 11 ("));
+
+        let both = text(session.execute(" run "));
+        assert!(both.contains("Part 1: 11 (") && both.contains("\nPart 2: 31 ("));
+
+        session.set_input("not a list".into(), "typed".into());
+        assert!(text(session.execute("1")).starts_with("Part 1: ✖ day 01 part 1 panicked"));
+        assert_eq!(session.execute(""), Reply::Text(String::new()));
+        assert_eq!(session.execute("exit"), Reply::Quit);
+    }
+
+    #[test]
+    fn runs_inspection_commands() {
+        let mut session = start(day!(15));
+        assert!(text(session.execute("help")).contains("\n\nDay 15:\n  show"));
+        assert!(text(session.execute("step 700")).ends_with("GPS sum 10092"));
+        assert!(matches!(session.execute("step x"), Reply::Error(_)));
+        assert!(
+            matches!(session.execute("steps"), Reply::Error(m) if m.contains("unknown command"))
+        );
+
+        // NOTE: switching inputs starts the inspection over.
+        session.set_input(read_file("examples", day!(15)), "example".into());
+        assert!(text(session.execute("show")).contains("after 0 of 700 moves"));
+
+        let mut session = start(day!(1));
+        assert!(!text(session.execute("help")).contains("Day 01:"));
+        assert!(matches!(session.execute("show"), Reply::Error(_)));
+    }
+}
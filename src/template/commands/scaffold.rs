@@ -1,10 +1,9 @@
 use std::{
     fs::{File, OpenOptions},
     io::Write,
-    process,
 };
 
-use crate::template::Day;
+use crate::template::{config, Day, Error, ErrorContext};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -27,53 +26,30 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+pub fn handle(day: Day, overwrite: bool) -> Result<(), Error> {
+    let paths = &config::get().paths;
+    let input_path = format!("{}/{day}.txt", paths.inputs);
+    let example_path = format!("{}/{day}.txt", paths.examples);
     let module_path = format!("src/bin/{day}.rs");
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    let mut file =
+        safe_create_file(&module_path, overwrite).context("Failed to create module file")?;
 
-    match file.write_all(
+    file.write_all(
         MODULE_TEMPLATE
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    )
+    .context("Failed to write module contents")?;
+    println!("Created module file \"{}\"", &module_path);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&input_path).context("Failed to create input file")?;
+    println!("Created empty input file \"{}\"", &input_path);
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&example_path).context("Failed to create example file")?;
+    println!("Created empty example file \"{}\"", &example_path);
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
 }
@@ -0,0 +1,184 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use crate::generate;
+use crate::template::run_multi::child_commands;
+use crate::template::{all_parts, config, input, output, Day, Error, ErrorContext};
+
+/// Tile factors used with `--tile` unless `--sizes` is given.
+const TILES: [usize; 4] = [1, 2, 4, 8];
+/// Fractions of a real input's size used for generated inputs unless `--sizes` is given.
+const FRACTIONS: [usize; 5] = [16, 8, 4, 2, 1];
+
+/// Where the inputs of the runs are written, one folder per size.
+const SCALE_DIR: &str = "target/scale";
+
+pub fn handle(
+    day: Day,
+    tile: bool,
+    sizes: Option<Vec<usize>>,
+    seed: Option<u64>,
+) -> Result<(), Error> {
+    let (unit, sizes) = if tile {
+        ("copies", sizes.unwrap_or(TILES.to_vec()))
+    } else {
+        let generator = generate::generator(day);
+        let sizes = sizes.unwrap_or_else(|| {
+            let mut sizes: Vec<usize> = FRACTIONS
+                .iter()
+                .map(|fraction| (generator.default_size / fraction).max(generator.min_size))
+                .collect();
+            sizes.dedup();
+            sizes
+        });
+        if let Some(size) = sizes.iter().find(|&&size| size < generator.min_size) {
+            return Err(Error::Usage(format!(
+                "day {day} needs a size of at least {} {}, got {size}",
+                generator.min_size, generator.unit
+            )));
+        }
+        (generator.unit, sizes)
+    };
+
+    if sizes.is_empty() || sizes.contains(&0) {
+        return Err(Error::Usage("sizes must be positive".into()));
+    }
+
+    let real_input = if tile {
+        let path = config::get()
+            .paths
+            .folder("inputs")
+            .join(format!("{day}.txt"));
+        let text = input::read(&path)
+            .with_context(|| format!("could not open input file \"{}\"", path.display()))?;
+        Some(text)
+    } else {
+        None
+    };
+
+    let (bold, reset) = (output::bold(), output::reset());
+    println!("{bold}Day {day}{reset}");
+    println!("------");
+    let width = unit.len().max(6);
+    println!(
+        "{unit:>width$}  {:>10}  {:>12}  {:>12}",
+        "bytes", "Part 1", "Part 2"
+    );
+
+    let mut samples: Vec<(f64, [Option<f64>; 2])> = vec![];
+    for &size in &sizes {
+        let text = match &real_input {
+            Some(text) => vec![text.as_str(); size].join("\n"),
+            None => generate::input(day, seed.unwrap_or(generate::DEFAULT_SEED), Some(size)),
+        };
+
+        let dir = PathBuf::from(SCALE_DIR)
+            .join(day.to_string())
+            .join(size.to_string());
+        fs::create_dir_all(&dir).context("Failed to create the scale directory")?;
+        fs::write(dir.join(format!("{day}.txt")), &text).context("Failed to write input file")?;
+
+        let args = [
+            "--set".to_string(),
+            format!("paths.inputs={}", dir.display()),
+        ];
+        let run = child_commands::bench_solution(day, &args)?;
+
+        let mut nanos = [None, None];
+        for (part, _, time) in child_commands::parse_part_times(&run) {
+            nanos[usize::from(part.into_inner() - 1)] = Some(time);
+        }
+        let format = |time: Option<f64>| {
+            time.map_or("-".into(), |nanos| {
+                format!("{:.1?}", Duration::from_nanos(nanos as u64))
+            })
+        };
+        println!(
+            "{size:>width$}  {:>10}  {:>12}  {:>12}",
+            text.len(),
+            format(nanos[0]),
+            format(nanos[1])
+        );
+        samples.push((text.len() as f64, nanos));
+    }
+
+    println!();
+    for (i, part) in all_parts().enumerate() {
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .filter_map(|(bytes, nanos)| Some((*bytes, nanos[i]?)))
+            .collect();
+        match fit_exponent(&points) {
+            Some(k) => println!(
+                "Part {part}: time ~ n^{k:.2}, about {bold}{}{reset}",
+                complexity(k)
+            ),
+            None => println!("Part {part}: not enough timings to estimate."),
+        }
+    }
+    println!("(n is the input length in bytes)");
+
+    Ok(())
+}
+
+/// Fits `y = c * x^k` to `points` by least squares on their logarithms and returns `k`.
+fn fit_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(x, y)| *x > 0.0 && *y > 0.0)
+        .map(|(x, y)| (x.ln(), y.ln()))
+        .collect();
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (logs.len() >= 2 && variance > 0.0).then(|| covariance / variance)
+}
+
+/// Names the complexity class closest to the exponent `k`.
+fn complexity(k: f64) -> String {
+    // NOTE: rounded to halves, timings are too noisy for more precision.
+    match (k * 2.0).round() as i64 {
+        ..=0 => "O(1)".into(),
+        2 => "O(n)".into(),
+        4 => "O(n²)".into(),
+        6 => "O(n³)".into(),
+        halves => format!("O(n^{})", halves as f64 / 2.0),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{complexity, fit_exponent};
+
+    #[test]
+    fn fits_power_laws() {
+        let quadratic: Vec<(f64, f64)> = [10.0, 20.0, 40.0, 80.0]
+            .iter()
+            .map(|&x| (x, 3.0 * x * x))
+            .collect();
+        let k = fit_exponent(&quadratic).unwrap();
+        assert!((k - 2.0).abs() < 1e-9, "{k}");
+        assert_eq!(complexity(k), "O(n²)");
+
+        let noisy = [(100.0, 105.0), (200.0, 190.0), (400.0, 410.0)];
+        assert_eq!(complexity(fit_exponent(&noisy).unwrap()), "O(n)");
+    }
+
+    #[test]
+    fn names_complexities() {
+        assert_eq!(complexity(0.1), "O(1)");
+        assert_eq!(complexity(1.4), "O(n^1.5)");
+        assert_eq!(complexity(3.1), "O(n³)");
+    }
+
+    #[test]
+    fn needs_two_sizes() {
+        assert_eq!(fit_exponent(&[(10.0, 1.0)]), None);
+        assert_eq!(fit_exponent(&[(10.0, 1.0), (10.0, 2.0)]), None);
+    }
+}
@@ -0,0 +1,562 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::config::{self, Serve};
+use crate::template::timings::Timings;
+use crate::template::{input, Day, Error, ErrorContext, Part};
+
+/// Port used when none is given.
+pub const DEFAULT_PORT: u16 = 8080;
+
+/// Largest accepted request line and headers.
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+
+/// Connections that may wait for each worker before no more are accepted.
+const QUEUE_PER_WORKER: usize = 4;
+
+pub fn handle(port: Option<u16>) -> Result<(), Error> {
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .with_context(|| format!("could not listen on port {port}"))?;
+    let address = listener.local_addr()?;
+
+    println!("Serving solutions on http://{address}/");
+    println!("  POST /solve/<day>/<part> with the input as body");
+    println!("  GET  /days");
+    serve(&listener, &config::get().serve);
+    Ok(())
+}
+
+/// Answers requests on `listener` with a fixed pool of `serve.workers` threads, so a burst of
+/// clients can't exhaust the process. While the workers and their queue are busy, no connection
+/// is accepted and further clients wait in the backlog of the listener.
+fn serve(listener: &TcpListener, limits: &Serve) {
+    let workers = usize::try_from(limits.workers).unwrap_or(1).max(1);
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(workers * QUEUE_PER_WORKER);
+    let receiver = Arc::new(Mutex::new(receiver));
+    let solvers = Solvers::new(workers);
+
+    for _ in 0..workers {
+        let receiver = Arc::clone(&receiver);
+        let limits = limits.clone();
+        let solvers = solvers.clone();
+        thread::spawn(move || loop {
+            // NOTE: the lock is released before responding, so other workers take the next ones.
+            let stream = match receiver.lock() {
+                Ok(receiver) => receiver.recv(),
+                Err(_) => return,
+            };
+            match stream {
+                Ok(stream) => respond(stream, &limits, &solvers),
+                Err(_) => return,
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if sender.send(stream).is_err() {
+                    return;
+                }
+            }
+            Err(e) => eprintln!("Could not accept a connection: {e}"),
+        }
+    }
+}
+
+/// Counts the solutions running on their own threads, including those whose request has timed
+/// out, and allows at most `max` at once.
+#[derive(Clone)]
+struct Solvers {
+    running: Arc<AtomicUsize>,
+    max: usize,
+}
+
+impl Solvers {
+    fn new(max: usize) -> Self {
+        Self {
+            running: Arc::new(AtomicUsize::new(0)),
+            max,
+        }
+    }
+
+    /// Reserves a solver, `None` if all of them are running. The solver is released when the
+    /// returned guard is dropped.
+    fn reserve(&self) -> Option<Solver> {
+        self.running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                (running < self.max).then_some(running + 1)
+            })
+            .ok()?;
+        Some(Solver(Arc::clone(&self.running)))
+    }
+}
+
+struct Solver(Arc<AtomicUsize>);
+
+impl Drop for Solver {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    body: JsonValue,
+}
+
+/// Reads one request from `stream` and answers it. The connection is closed afterwards.
+fn respond(mut stream: TcpStream, limits: &Serve, solvers: &Solvers) {
+    let timer = Instant::now();
+    let deadline = timer + Duration::from_millis(limits.timeout_ms);
+    let max_body = usize::try_from(limits.max_body_kb * 1024).unwrap_or(usize::MAX);
+
+    let (label, response) = match read_request(&mut stream, deadline, max_body) {
+        Ok(request) => (
+            format!("{} {}", request.method, request.path),
+            route(&request, deadline, solvers),
+        ),
+        Err(response) => ("(invalid request)".into(), response),
+    };
+
+    let body = response.body.stringify().unwrap_or_default();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    let written = stream
+        .write_all(head.as_bytes())
+        .and_then(|()| stream.write_all(body.as_bytes()));
+
+    println!("{label} {} ({:.1?})", response.status, timer.elapsed());
+    if let Err(e) = written {
+        eprintln!("Could not send the response: {e}");
+    }
+}
+
+fn read_request(
+    stream: &mut TcpStream,
+    deadline: Instant,
+    max_body: usize,
+) -> Result<Request, Response> {
+    let mut buffer: Vec<u8> = vec![];
+    let mut chunk = [0; 4096];
+
+    let head_len = loop {
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            return Err(error(431, "the request headers are too large"));
+        }
+        match read_before(stream, &mut chunk, deadline)? {
+            0 => return Err(error(400, "the request ended before its headers")),
+            n => buffer.extend_from_slice(&chunk[..n]),
+        }
+    };
+
+    let head = std::str::from_utf8(&buffer[..head_len])
+        .map_err(|_| error(400, "the request headers are not UTF-8"))?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let (Some(method), Some(target), Some(_)) = (
+        request_line.next(),
+        request_line.next(),
+        request_line.next(),
+    ) else {
+        return Err(error(400, "malformed request line"));
+    };
+
+    let mut content_length = None;
+    for line in lines {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| error(400, "malformed header"))?;
+        if name.eq_ignore_ascii_case("content-length") {
+            let length = value.trim().parse::<usize>();
+            content_length = Some(length.map_err(|_| error(400, "invalid Content-Length"))?);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(error(
+                411,
+                "chunked bodies are not supported, send a Content-Length",
+            ));
+        }
+    }
+
+    let length = match (method, content_length) {
+        ("POST", None) => return Err(error(411, "a Content-Length is required")),
+        (_, length) => length.unwrap_or(0),
+    };
+    if length > max_body {
+        return Err(error(
+            413,
+            &format!("the body exceeds the limit of {} KB", max_body / 1024),
+        ));
+    }
+
+    let mut body = buffer[head_len + 4..].to_vec();
+    while body.len() < length {
+        match read_before(stream, &mut chunk, deadline)? {
+            0 => return Err(error(400, "the request ended before its body")),
+            n => body.extend_from_slice(&chunk[..n]),
+        }
+    }
+    body.truncate(length);
+
+    // NOTE: query strings are ignored.
+    let path = target.split('?').next().unwrap_or_default();
+    Ok(Request {
+        method: method.into(),
+        path: path.into(),
+        body,
+    })
+}
+
+/// Reads from `stream` into `buf`, failing once `deadline` has passed.
+fn read_before(
+    stream: &mut TcpStream,
+    buf: &mut [u8],
+    deadline: Instant,
+) -> Result<usize, Response> {
+    let timed_out = || error(408, "timed out reading the request");
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if remaining.is_zero() {
+        return Err(timed_out());
+    }
+    stream
+        .set_read_timeout(Some(remaining))
+        .map_err(|e| error(500, &e.to_string()))?;
+
+    match stream.read(buf) {
+        Ok(n) => Ok(n),
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            Err(timed_out())
+        }
+        Err(e) => Err(error(400, &format!("could not read the request: {e}"))),
+    }
+}
+
+fn route(request: &Request, deadline: Instant, solvers: &Solvers) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["solve", day, part]) => solve(day, part, &request.body, deadline, solvers),
+        ("GET", ["days"]) => days(),
+        (_, ["solve", _, _] | ["days"]) => error(405, "method not allowed"),
+        _ => error(404, "not found"),
+    }
+}
+
+/// Solves a part for the posted input. Responds with the answer, the time it took in
+/// nanoseconds and an error, each `null` if not applicable.
+fn solve(day: &str, part: &str, body: &[u8], deadline: Instant, solvers: &Solvers) -> Response {
+    let parsed = day
+        .parse::<Day>()
+        .map_err(Error::from)
+        .and_then(|day| Ok((day, part.parse::<Part>()?)));
+    let (day, part) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return solution(400, None, None, Some(e.to_string())),
+    };
+
+    let Ok(text) = std::str::from_utf8(body) else {
+        return solution(400, None, None, Some("the input is not UTF-8".into()));
+    };
+    let text = input::normalize(text, &config::get().input).0.into_owned();
+
+    // NOTE: a solution that times out keeps running on its thread, threads can't be stopped. It
+    // holds on to its solver until it finishes, so these threads can't pile up.
+    let Some(solver) = solvers.reserve() else {
+        let message = "all solvers are busy, some with requests that timed out";
+        return solution(503, None, None, Some(message.into()));
+    };
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let timer = Instant::now();
+        let result = crate::solve(day, part, &text);
+        let elapsed = timer.elapsed();
+        drop(solver);
+        let _ = sender.send((result, elapsed));
+    });
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match receiver.recv_timeout(remaining) {
+        Ok((Ok(answer), elapsed)) => solution(200, Some(answer.to_string()), Some(elapsed), None),
+        Ok((Err(e @ Error::Usage(_)), _)) => solution(404, None, None, Some(e.to_string())),
+        Ok((Err(e), elapsed)) => solution(422, None, Some(elapsed), Some(e.to_string())),
+        Err(_) => solution(504, None, None, Some("timed out solving the input".into())),
+    }
+}
+
+fn solution(
+    status: u16,
+    answer: Option<String>,
+    elapsed: Option<Duration>,
+    error: Option<String>,
+) -> Response {
+    let nanos = elapsed.map_or(JsonValue::Null, |elapsed| {
+        JsonValue::Number(elapsed.as_nanos() as f64)
+    });
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "answer".into(),
+        answer.map_or(JsonValue::Null, JsonValue::String),
+    );
+    map.insert("nanos".into(), nanos);
+    map.insert(
+        "error".into(),
+        error.map_or(JsonValue::Null, JsonValue::String),
+    );
+
+    Response {
+        status,
+        body: JsonValue::Object(map),
+    }
+}
+
+/// Lists the days that have a solution, with their parts and stored timings.
+fn days() -> Response {
+    let timings = Timings::read_from_file();
+
+    let days = crate::days()
+        .iter()
+        .map(|solution| {
+            let parts = solution
+                .parts
+                .iter()
+                .map(|part| JsonValue::Number(f64::from(part.into_inner())))
+                .collect();
+            let timing = timings
+                .data
+                .iter()
+                .find(|timing| timing.day == solution.day)
+                .map_or(JsonValue::Null, JsonValue::from);
+
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("day".into(), JsonValue::String(solution.day.to_string()));
+            map.insert("parts".into(), JsonValue::Array(parts));
+            map.insert("timing".into(), timing);
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("days".into(), JsonValue::Array(days));
+    Response {
+        status: 200,
+        body: JsonValue::Object(map),
+    }
+}
+
+fn error(status: u16, message: &str) -> Response {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("error".into(), JsonValue::String(message.into()));
+    Response {
+        status,
+        body: JsonValue::Object(map),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{Read, Write},
+        net::{SocketAddr, TcpListener, TcpStream},
+        str::FromStr,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use tinyjson::JsonValue;
+
+    use super::{serve, solve, Solvers};
+    use crate::template::config::Serve;
+
+    fn start(timeout_ms: u64) -> SocketAddr {
+        start_with(timeout_ms, 4)
+    }
+
+    fn start_with(timeout_ms: u64, workers: u64) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let limits = Serve {
+            max_body_kb: 1,
+            timeout_ms,
+            workers,
+        };
+        thread::spawn(move || serve(&listener, &limits));
+        address
+    }
+
+    /// Sends `request` as is and returns the status and the JSON body of the response.
+    fn send(address: SocketAddr, request: &str) -> (u16, HashMap<String, JsonValue>) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        let json = JsonValue::from_str(body).unwrap();
+        (status, json.get::<HashMap<_, _>>().unwrap().clone())
+    }
+
+    fn post(path: &str, body: &str) -> String {
+        format!(
+            "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[test]
+    fn solves_posted_inputs() {
+        let address = start(5000);
+        let (status, json) = send(
+            address,
+            &post("/solve/1/1", "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n"),
+        );
+
+        assert_eq!(status, 200);
+        assert_eq!(json["answer"], JsonValue::String("11".into()));
+        assert!(json["nanos"].is_number());
+        assert!(json["error"].is_null());
+
+        let (status, json) = send(address, &post("/solve/01/1", "no list"));
+        assert_eq!(status, 422);
+        assert!(json["answer"].is_null());
+        assert!(json["error"].is_string());
+    }
+
+    #[test]
+    fn lists_days() {
+        let address = start(5000);
+        let (status, json) = send(address, "GET /days HTTP/1.1\r\n\r\n");
+
+        assert_eq!(status, 200);
+        let days: &Vec<JsonValue> = json["days"].get().unwrap();
+        assert_eq!(days.len(), 25);
+        assert_eq!(days[0]["day"], JsonValue::String("01".into()));
+        assert_eq!(
+            days[0]["parts"],
+            JsonValue::Array(vec![JsonValue::Number(1.0), JsonValue::Number(2.0)])
+        );
+    }
+
+    #[test]
+    fn rejects_bad_requests() {
+        let address = start(5000);
+
+        let cases = [
+            ("GET /solve/1/1 HTTP/1.1\r\n\r\n".to_string(), 405),
+            ("GET /nope HTTP/1.1\r\n\r\n".to_string(), 404),
+            (post("/solve/26/1", "1"), 400),
+            (post("/solve/1/3", "1"), 400),
+            ("POST /solve/1/1 HTTP/1.1\r\n\r\n".to_string(), 411),
+            (
+                "POST /solve/1/1 HTTP/1.1\r\nContent-Length: 4096\r\n\r\n".to_string(),
+                413,
+            ),
+            ("nonsense\r\n\r\n".to_string(), 400),
+        ];
+        for (request, expected) in cases {
+            let (status, json) = send(address, &request);
+            assert_eq!(status, expected, "{request:?}");
+            assert!(json["error"].is_string());
+        }
+    }
+
+    #[test]
+    fn times_out_slow_requests() {
+        let address = start(100);
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"POST /solve/1/1 HTTP/1.1\r\nContent-Length: 10\r\n\r\n1")
+            .unwrap();
+        thread::sleep(Duration::from_millis(200));
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 408 "), "{response}");
+    }
+
+    #[test]
+    fn queues_requests_beyond_the_workers() {
+        // NOTE: an incomplete request keeps the single worker busy until it times out.
+        let address = start_with(200, 1);
+        let mut slow = TcpStream::connect(address).unwrap();
+        slow.write_all(b"POST /solve/1/1 HTTP/1.1\r\n").unwrap();
+        thread::sleep(Duration::from_millis(20));
+
+        let timer = Instant::now();
+        let (status, _) = send(address, "GET /days HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 200);
+        assert!(timer.elapsed() >= Duration::from_millis(100));
+
+        let mut response = String::new();
+        slow.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 408 "), "{response}");
+    }
+
+    #[test]
+    fn limits_running_solvers() {
+        let solvers = Solvers::new(1);
+        let solver = solvers.reserve().unwrap();
+        assert!(solvers.reserve().is_none());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let response = solve("1", "1", b"3   4\n", deadline, &solvers);
+        assert_eq!(response.status, 503);
+
+        drop(solver);
+        let response = solve("1", "1", b"3   4\n", deadline, &solvers);
+        assert_eq!(response.status, 200);
+        assert!(solvers.reserve().is_some());
+    }
+}
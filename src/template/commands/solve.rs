@@ -1,8 +1,45 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{config, output, Day, Error, ErrorContext, Part};
+use crate::viz;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// What a solution reports besides its answers: traces and frames.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    /// `--trace`: write the solution's traces.
+    pub trace: bool,
+    /// `--viz <target>`: animate the solution's frames.
+    pub viz: Option<viz::Target>,
+    /// `--fps <n>`: frames per second of the animation.
+    pub fps: Option<u64>,
+}
+
+impl Diagnostics {
+    /// The arguments that enable these diagnostics in a solution binary.
+    fn forwarded_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.trace {
+            args.push("--trace".to_string());
+        }
+        if let Some(viz) = &self.viz {
+            args.extend(["--viz".to_string(), viz.to_string()]);
+        }
+        if let Some(fps) = self.fps {
+            args.extend(["--fps".to_string(), fps.to_string()]);
+        }
+        args
+    }
+}
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    verbose: bool,
+    submit_part: Option<Part>,
+    variant: Option<String>,
+    diagnostics: Diagnostics,
+) -> Result<(), Error> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -17,6 +54,19 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    cmd_args.extend(config::forwarded_args());
+    cmd_args.extend(output::forwarded_args());
+
+    if verbose {
+        cmd_args.push("--verbose".to_string());
+    }
+
+    cmd_args.extend(diagnostics.forwarded_args());
+
+    if let Some(variant) = variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant);
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
@@ -28,7 +78,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
-        .unwrap();
+        .map_err(Error::from_cargo_spawn)?;
+
+    let status = cmd.wait().context("could not wait for solution")?;
+
+    if !status.success() {
+        return Err(Error::from_solution_status(day, status));
+    }
 
-    cmd.wait().unwrap();
+    Ok(())
 }
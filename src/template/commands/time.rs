@@ -1,10 +1,22 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, path::Path, time::Duration};
 
-use crate::template::run_multi::run_multi;
+use crate::template::run_multi::{child_commands, get_path_for_bin, run_multi};
+use crate::template::runner::DEFAULT_VARIANT;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, all_parts, config, output, readme_benchmarks, Day, Error, ErrorContext, Part,
+};
+
+pub fn handle(day: Option<Day>, run_all: bool, store: bool, variants: bool) -> Result<(), Error> {
+    if variants {
+        if store {
+            return Err(Error::Usage(
+                "`--store` can't be combined with `--variants`".into(),
+            ));
+        }
+        return bench_variants(day);
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,20 +33,160 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run = run_multi(&days_to_run, true, true)?;
 
     if store {
+        // NOTE: timings of successful days are stored even if other days failed.
+        let timings = run.timings.clone().unwrap_or_default();
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file().with_context(|| {
+            format!(
+                "could not write timings to \"{}\"",
+                config::get().paths.timings
+            )
+        })?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+        readme_benchmarks::update(merged_timings).context("Failed to store updated benchmarks.")?;
+        println!("Stored updated benchmarks.");
+    }
+
+    run.check()
+}
+
+/// Result of benching one variant of a solution.
+struct VariantRun {
+    name: String,
+    /// Answer and time in nanoseconds of each part the variant solves.
+    parts: Vec<(Part, Option<String>, f64)>,
+}
+
+/// Benches every variant of `day`, or of all days that have variants, and fails if variants
+/// disagree on an answer.
+fn bench_variants(day: Option<Day>) -> Result<(), Error> {
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days()
+            .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+            .collect(),
+    };
+
+    let mut disagreements: Vec<String> = vec![];
+    let mut benched = 0;
+
+    for day in days {
+        let variants = child_commands::capture_solution(day, true, &["--variants".into()])?;
+        // NOTE: the default variant is always listed first.
+        if variants.len() < 2 {
+            continue;
+        }
+
+        if benched > 0 {
+            println!();
+        }
+        benched += 1;
+
+        let mut runs: Vec<VariantRun> = vec![];
+        for name in variants {
+            let args = ["--variant".to_string(), name.clone()];
+            let output = child_commands::bench_solution(day, &args)?;
+            let answers = child_commands::parse_answers(&output);
+            let parts = child_commands::parse_part_times(&output)
+                .into_iter()
+                .map(|(part, _, nanos)| {
+                    let answer = answers.iter().find(|(p, _)| *p == part);
+                    (part, answer.and_then(|(_, answer)| answer.clone()), nanos)
+                })
+                .collect();
+            runs.push(VariantRun { name, parts });
+        }
+
+        disagreements.extend(print_variants(day, &runs));
+    }
+
+    if benched == 0 {
+        return Err(Error::Usage(match day {
+            Some(day) => format!("day {day} has no variants"),
+            None => "no solution has variants".into(),
+        }));
+    }
+
+    if disagreements.is_empty() {
+        return Ok(());
+    }
+
+    println!(
+        "\n{}{} answer(s) disagree with the {DEFAULT_VARIANT} variant:{}",
+        output::bold(),
+        disagreements.len(),
+        output::reset()
+    );
+    for disagreement in &disagreements {
+        println!("  {disagreement}");
+    }
+    Err(Error::Solution(format!(
+        "variants disagree on {} answer(s)",
+        disagreements.len()
+    )))
+}
+
+/// Prints the timings of the variants of a day side by side, relative to the first variant that
+/// solves a part. Returns the answers that disagree with that variant.
+fn print_variants(day: Day, runs: &[VariantRun]) -> Vec<String> {
+    let (bold, reset) = (output::bold(), output::reset());
+    println!("{bold}Day {day}{reset}");
+    println!("------");
+
+    let width = runs
+        .iter()
+        .map(|run| run.name.len())
+        .chain(["variant".len()])
+        .max()
+        .unwrap_or_default();
+    println!("{:<width$}  {:<22}  Part 2", "variant", "Part 1");
+
+    let mut disagreements = vec![];
+    let mut cells: Vec<Vec<String>> = runs.iter().map(|_| vec![]).collect();
+
+    for part in all_parts() {
+        let results: Vec<Option<&(Part, Option<String>, f64)>> = runs
+            .iter()
+            .map(|run| run.parts.iter().find(|(p, _, _)| *p == part))
+            .collect();
+        let Some(reference) = results.iter().position(Option::is_some) else {
+            cells.iter_mut().for_each(|cells| cells.push("-".into()));
+            continue;
+        };
+        let (_, expected, reference_nanos) = results[reference].unwrap();
+
+        for (i, result) in results.iter().enumerate() {
+            let Some((_, answer, nanos)) = result else {
+                cells[i].push("-".into());
+                continue;
+            };
+
+            let mut cell = format!("{:.1?}", Duration::from_nanos(*nanos as u64));
+            if i != reference {
+                cell.push_str(&format!(" ({:.1}×)", nanos / reference_nanos));
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            if answer != expected {
+                let shown =
+                    |answer: &Option<String>| answer.clone().unwrap_or_else(|| "no result".into());
+                disagreements.push(format!(
+                    "day {day} part {part} with {}: got {}, expected {}",
+                    runs[i].name,
+                    shown(answer),
+                    shown(expected)
+                ));
+                cell.push_str(" ✗");
             }
+            cells[i].push(cell);
         }
     }
+
+    for (run, cells) in runs.iter().zip(cells) {
+        println!("{:<width$}  {:<22}  {}", run.name, cells[0], cells[1]);
+    }
+
+    disagreements
 }
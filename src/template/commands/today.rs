@@ -0,0 +1,291 @@
+use std::{io::Write, path::Path, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+use crate::template::clock::{Clock, SystemClock};
+use crate::template::commands::{download, read, scaffold};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{output, Day, Error};
+
+/// Delay before the first download retry. It doubles after every failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+/// Upper bound for the delay between download attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Number of download attempts before giving up.
+const MAX_ATTEMPTS: u32 = 8;
+
+pub fn handle(wait: bool) -> Result<(), Error> {
+    let clock = SystemClock;
+
+    let day = if wait {
+        let (day, unlock) = day_to_wait_for(clock.now(), |day| {
+            Path::new(&get_path_for_bin(day)).exists()
+        })?;
+        if let Some(unlock) = unlock {
+            countdown(&clock, day, unlock, output::interactive());
+        }
+        day
+    } else {
+        Day::today_at(clock.now()).ok_or_else(|| {
+            Error::Usage(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day \
+                or `today --wait` to wait for the next puzzle."
+                    .into(),
+            )
+        })?
+    };
+
+    scaffold::handle(day, false)?;
+    if wait {
+        with_retry(&clock, || download::handle(day))?;
+    } else {
+        download::handle(day)?;
+    }
+    read::handle(day)
+}
+
+/// The day `--wait` works on: today's puzzle if it is unlocked but not scaffolded yet, so it isn't
+/// skipped, otherwise the next one to unlock along with its unlock time.
+fn day_to_wait_for(
+    now: DateTime<Utc>,
+    scaffolded: impl Fn(Day) -> bool,
+) -> Result<(Day, Option<DateTime<Utc>>), Error> {
+    if let Some(today) = Day::today_at(now).filter(|day| !scaffolded(*day)) {
+        return Ok((today, None));
+    }
+
+    let (day, unlock) = Day::next_unlock(now).ok_or_else(|| {
+        Error::Usage(
+            "all puzzles of this year have been unlocked. \
+            Please use `scaffold` with a specific day."
+                .into(),
+        )
+    })?;
+    Ok((day, Some(unlock)))
+}
+
+/// Interval between countdown updates when the countdown line can't be overwritten.
+const LINE_PROGRESS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Blocks until `unlock`. Updates a countdown line every second if `interactive`,
+/// otherwise prints the remaining time on a new line every minute.
+fn countdown(clock: &impl Clock, day: Day, unlock: DateTime<Utc>, interactive: bool) {
+    let interval = if interactive {
+        Duration::from_secs(1)
+    } else {
+        LINE_PROGRESS_INTERVAL
+    };
+
+    while let Ok(remaining) = (unlock - clock.now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+
+        if interactive {
+            print!("\r⏳ Day {day} unlocks in {}", format_remaining(remaining));
+            let _ = std::io::stdout().flush();
+        } else {
+            println!("⏳ Day {day} unlocks in {}", format_remaining(remaining));
+        }
+        clock.sleep(remaining.min(interval));
+    }
+
+    if interactive {
+        println!("\r🔓 Day {day} is unlocked!{:16}", "");
+    } else {
+        println!("🔓 Day {day} is unlocked!");
+    }
+}
+
+/// Runs `f` until it succeeds, backing off exponentially after failed attempts.
+/// Only failures of the aoc-cli call itself are retried; e.g. a missing tool fails immediately.
+fn with_retry(clock: &impl Clock, mut f: impl FnMut() -> Result<(), Error>) -> Result<(), Error> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match f() {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < MAX_ATTEMPTS && is_retryable(&err) => {
+                eprintln!(
+                    "Download failed (attempt {attempt}/{MAX_ATTEMPTS}), retrying in {}s...",
+                    backoff.as_secs()
+                );
+                clock.sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn is_retryable(err: &Error) -> bool {
+    err.chain()
+        .last()
+        .is_some_and(|err| matches!(err, Error::Command { .. }))
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    use chrono::TimeZone;
+
+    struct FakeClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: DateTime<Utc>) -> Self {
+            Self {
+                now: Cell::new(now),
+                sleeps: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn command_error() -> Error {
+        #[cfg(unix)]
+        use std::os::unix::process::ExitStatusExt;
+        #[cfg(windows)]
+        use std::os::windows::process::ExitStatusExt;
+
+        Error::Command {
+            command: "aoc download".into(),
+            status: std::process::ExitStatus::from_raw(1),
+        }
+    }
+
+    #[test]
+    fn counts_down_to_unlock() {
+        let unlock = Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap();
+        let clock = FakeClock::at(unlock - Duration::from_millis(2500));
+
+        countdown(&clock, Day::new(5).unwrap(), unlock, true);
+
+        assert_eq!(clock.now(), unlock);
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+                Duration::from_millis(500)
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_down_in_lines() {
+        let unlock = Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap();
+        let clock = FakeClock::at(unlock - Duration::from_secs(150));
+
+        countdown(&clock, Day::new(5).unwrap(), unlock, false);
+
+        assert_eq!(clock.now(), unlock);
+        let sleeps: Vec<_> = clock
+            .sleeps
+            .borrow()
+            .iter()
+            .map(Duration::as_secs)
+            .collect();
+        assert_eq!(sleeps, [60, 60, 30]);
+    }
+
+    #[test]
+    fn takes_todays_puzzle_unless_scaffolded() {
+        let day = |n| Day::new(n).unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 12, 5, 12, 0, 0).unwrap();
+
+        let (today, unlock) = day_to_wait_for(now, |_| false).unwrap();
+        assert_eq!((today, unlock), (day(5), None));
+
+        let (next, unlock) = day_to_wait_for(now, |d| d == day(5)).unwrap();
+        assert_eq!(next, day(6));
+        assert_eq!(unlock, day(6).unlock_time(2024));
+
+        let before = Utc.with_ymd_and_hms(2024, 11, 30, 12, 0, 0).unwrap();
+        assert_eq!(day_to_wait_for(before, |_| false).unwrap().0, day(1));
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = FakeClock::at(Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap());
+        let mut calls = 0;
+
+        let result = with_retry(&clock, || {
+            calls += 1;
+            if calls < 4 {
+                Err(command_error())
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(calls, 4);
+        let sleeps: Vec<_> = clock
+            .sleeps
+            .borrow()
+            .iter()
+            .map(Duration::as_secs)
+            .collect();
+        assert_eq!(sleeps, [2, 4, 8]);
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let clock = FakeClock::at(Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap());
+        let mut calls = 0;
+
+        let result = with_retry(&clock, || {
+            calls += 1;
+            Err(command_error())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls, MAX_ATTEMPTS);
+        assert!(clock.sleeps.borrow().iter().all(|d| *d <= MAX_BACKOFF));
+    }
+
+    #[test]
+    fn does_not_retry_other_errors() {
+        let clock = FakeClock::at(Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap());
+        let mut calls = 0;
+
+        let result = with_retry(&clock, || {
+            calls += 1;
+            Err(Error::Usage("nope".into()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn formats_remaining_time() {
+        assert_eq!(format_remaining(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(format_remaining(Duration::from_millis(500)), "00:00:01");
+    }
+}
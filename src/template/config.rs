@@ -0,0 +1,687 @@
+//! Project configuration, loaded from `aoc.toml` in the repository root.
+//!
+//! The file is optional: every key has a default matching the template's conventions.
+//! Values can be overridden from the command-line with `--config <path>` and `--set <key>=<value>`,
+//! these overrides are forwarded to the solution binaries spawned by the template.
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::trace::Level;
+
+pub const DEFAULT_CONFIG_PATH: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Loaded> = OnceLock::new();
+
+struct Loaded {
+    config: Config,
+    path: Option<String>,
+    overrides: Vec<String>,
+}
+
+/// Documented keys of `aoc.toml`. See the file in the repository root for an annotated example.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// `aoc.year`: the puzzle year passed to aoc-cli. Defaults to the year aoc-cli infers.
+    pub year: Option<u16>,
+    pub paths: Paths,
+    pub readme: Readme,
+    pub bench: Bench,
+    pub cli: Cli,
+    pub input: Input,
+    pub serve: Serve,
+    pub trace: Trace,
+    pub viz: Viz,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Paths {
+    /// `paths.inputs`: directory containing puzzle inputs.
+    pub inputs: String,
+    /// `paths.examples`: directory containing example inputs.
+    pub examples: String,
+    /// `paths.puzzles`: directory containing puzzle descriptions.
+    pub puzzles: String,
+    /// `paths.timings`: file that stores benchmark results.
+    pub timings: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Readme {
+    /// `readme.path`: file that receives the benchmark table.
+    pub path: String,
+    /// `readme.marker`: marker that surrounds the benchmark table.
+    pub marker: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bench {
+    /// `bench.budget_ms`: approximate time spent benching a single part.
+    pub budget_ms: u64,
+    /// `bench.min_samples`: minimum number of bench iterations.
+    pub min_samples: u64,
+    /// `bench.max_samples`: maximum number of bench iterations.
+    pub max_samples: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cli {
+    /// `cli.strict`: treat unknown command-line arguments as errors.
+    pub strict: bool,
+}
+
+/// Normalization applied to inputs before they are passed to a solution, see [`crate::template::input`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    /// `input.strip_bom`: remove a leading byte order mark.
+    pub strip_bom: bool,
+    /// `input.line_endings`: convert `\r\n` line endings to `\n`.
+    pub line_endings: bool,
+    /// `input.trim_end`: remove whitespace at the end of the input, including the final newline.
+    pub trim_end: bool,
+}
+
+/// Limits of the `serve` command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Serve {
+    /// `serve.max_body_kb`: largest accepted request body, in kilobytes.
+    pub max_body_kb: u64,
+    /// `serve.timeout_ms`: time allowed to read a request and to solve it.
+    pub timeout_ms: u64,
+    /// `serve.workers`: requests answered, and solutions run, at the same time.
+    pub workers: u64,
+}
+
+/// Tracing of solutions run with `--trace`, see [`crate::trace`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+    /// `trace.level`: the most detailed level written.
+    pub level: Level,
+    /// `trace.file`: file that receives the traces instead of stderr. `{day}` is replaced with the day.
+    pub file: Option<String>,
+}
+
+/// Visualizations of solutions run with `--viz`, see [`crate::viz`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Viz {
+    /// `viz.fps`: frames per second of animations, unless `--fps` is passed.
+    pub fps: u64,
+    /// `viz.scale`: size of a grid cell in the pixels of an image.
+    pub scale: u64,
+    /// `viz.max_frames`: the most frames kept, further ones thin out the animation.
+    pub max_frames: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            paths: Paths {
+                inputs: "data/inputs".into(),
+                examples: "data/examples".into(),
+                puzzles: "data/puzzles".into(),
+                timings: "data/timings.json".into(),
+            },
+            readme: Readme {
+                path: "README.md".into(),
+                marker: "<!--- benchmarking table --->".into(),
+            },
+            bench: Bench {
+                budget_ms: 1000,
+                min_samples: 10,
+                max_samples: 10000,
+            },
+            cli: Cli { strict: false },
+            input: Input {
+                strip_bom: true,
+                line_endings: true,
+                trim_end: true,
+            },
+            serve: Serve {
+                max_body_kb: 1024,
+                timeout_ms: 10_000,
+                workers: 4,
+            },
+            trace: Trace {
+                level: Level::Debug,
+                file: None,
+            },
+            viz: Viz {
+                fps: 10,
+                scale: 4,
+                max_frames: 1000,
+            },
+        }
+    }
+}
+
+impl Paths {
+    /// Resolves a data folder name (e.g. `"inputs"`) to its configured directory.
+    pub fn folder(&self, folder: &str) -> PathBuf {
+        match folder {
+            "inputs" => PathBuf::from(&self.inputs),
+            "examples" => PathBuf::from(&self.examples),
+            "puzzles" => PathBuf::from(&self.puzzles),
+            other => Path::new("data").join(other),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when loading the configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    /// Where the bad value came from, e.g. `aoc.toml:4` or `--set`.
+    pub origin: String,
+    /// The offending key, if the error could be attributed to one.
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "{}: `{key}`: {}", self.origin, self.message),
+            None => write!(f, "{}: {}", self.origin, self.message),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Loads the configuration and applies command-line overrides. Must be called before [`get`] to take effect.
+///
+/// `path` defaults to [`DEFAULT_CONFIG_PATH`], which may be absent. An explicitly passed path must exist.
+pub fn init(path: Option<&str>, overrides: &[String]) -> Result<&'static Config, ConfigError> {
+    let config = load(path, overrides)?;
+    let loaded = CONFIG.get_or_init(|| Loaded {
+        config,
+        path: path.map(String::from),
+        overrides: overrides.to_vec(),
+    });
+    Ok(&loaded.config)
+}
+
+/// Returns the active configuration, loading `aoc.toml` on first access.
+///
+/// # Panics
+/// Panics with a message pointing at the bad key if the configuration file is invalid.
+pub fn get() -> &'static Config {
+    if let Some(loaded) = CONFIG.get() {
+        return &loaded.config;
+    }
+    match init(None, &[]) {
+        Ok(config) => config,
+        Err(e) => panic!("invalid configuration: {e}"),
+    }
+}
+
+/// Returns the `--config` and `--set` arguments that reproduce the active configuration in a child process.
+pub fn forwarded_args() -> Vec<String> {
+    let Some(loaded) = CONFIG.get() else {
+        return vec![];
+    };
+
+    let mut args = vec![];
+    if let Some(path) = &loaded.path {
+        args.extend(["--config".into(), path.clone()]);
+    }
+    for value in &loaded.overrides {
+        args.extend(["--set".into(), value.clone()]);
+    }
+    args
+}
+
+fn load(path: Option<&str>, overrides: &[String]) -> Result<Config, ConfigError> {
+    let mut config = Config::default();
+
+    let file_path = path.unwrap_or(DEFAULT_CONFIG_PATH);
+    match fs::read_to_string(file_path) {
+        Ok(contents) => {
+            for entry in parse(&contents, file_path)? {
+                config.apply(&entry)?;
+            }
+        }
+        Err(e) if path.is_none() && e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => {
+            return Err(ConfigError {
+                origin: file_path.into(),
+                key: None,
+                message: format!("could not read file: {e}"),
+            })
+        }
+    }
+
+    for value in overrides {
+        config.apply(&parse_override(value)?)?;
+    }
+
+    config.validate(file_path)?;
+    Ok(config)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{s:?}"),
+            Value::Integer(i) => write!(f, "{i}"),
+            Value::Boolean(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    origin: String,
+    key: String,
+    value: Value,
+}
+
+impl Entry {
+    fn error(&self, message: impl Into<String>) -> ConfigError {
+        ConfigError {
+            origin: self.origin.clone(),
+            key: Some(self.key.clone()),
+            message: message.into(),
+        }
+    }
+
+    fn string(&self) -> Result<String, ConfigError> {
+        match &self.value {
+            Value::String(s) if !s.is_empty() => Ok(s.clone()),
+            Value::String(_) => Err(self.error("expected a non-empty string")),
+            v => Err(self.error(format!("expected a string, found `{v}`"))),
+        }
+    }
+
+    fn boolean(&self) -> Result<bool, ConfigError> {
+        match &self.value {
+            Value::Boolean(b) => Ok(*b),
+            v => Err(self.error(format!("expected `true` or `false`, found `{v}`"))),
+        }
+    }
+
+    fn positive(&self) -> Result<u64, ConfigError> {
+        match &self.value {
+            Value::Integer(i) if *i > 0 => Ok(*i as u64),
+            v => Err(self.error(format!("expected a positive integer, found `{v}`"))),
+        }
+    }
+}
+
+impl Config {
+    fn apply(&mut self, entry: &Entry) -> Result<(), ConfigError> {
+        match entry.key.as_str() {
+            "aoc.year" => {
+                let year = entry.positive()?;
+                if !(2015..=9999).contains(&year) {
+                    return Err(
+                        entry.error(format!("expected a year from 2015 on, found `{year}`"))
+                    );
+                }
+                self.year = Some(year as u16);
+            }
+            "paths.inputs" => self.paths.inputs = entry.string()?,
+            "paths.examples" => self.paths.examples = entry.string()?,
+            "paths.puzzles" => self.paths.puzzles = entry.string()?,
+            "paths.timings" => self.paths.timings = entry.string()?,
+            "readme.path" => self.readme.path = entry.string()?,
+            "readme.marker" => self.readme.marker = entry.string()?,
+            "bench.budget_ms" => self.bench.budget_ms = entry.positive()?,
+            "bench.min_samples" => self.bench.min_samples = entry.positive()?,
+            "bench.max_samples" => self.bench.max_samples = entry.positive()?,
+            "cli.strict" => self.cli.strict = entry.boolean()?,
+            "input.strip_bom" => self.input.strip_bom = entry.boolean()?,
+            "input.line_endings" => self.input.line_endings = entry.boolean()?,
+            "input.trim_end" => self.input.trim_end = entry.boolean()?,
+            "serve.max_body_kb" => self.serve.max_body_kb = entry.positive()?,
+            "serve.timeout_ms" => self.serve.timeout_ms = entry.positive()?,
+            "serve.workers" => self.serve.workers = entry.positive()?,
+            "trace.level" => {
+                self.trace.level = entry.string()?.parse().map_err(|e| entry.error(e))?;
+            }
+            "trace.file" => self.trace.file = Some(entry.string()?),
+            "viz.fps" => self.viz.fps = entry.positive()?,
+            "viz.scale" => self.viz.scale = entry.positive()?,
+            "viz.max_frames" => self.viz.max_frames = entry.positive()?,
+            _ => return Err(entry.error("unknown key")),
+        }
+
+        Ok(())
+    }
+
+    /// Checks constraints that span multiple keys.
+    fn validate(&self, origin: &str) -> Result<(), ConfigError> {
+        if self.bench.min_samples > self.bench.max_samples {
+            return Err(ConfigError {
+                origin: origin.into(),
+                key: Some("bench.min_samples".into()),
+                message: format!(
+                    "must not exceed `bench.max_samples` ({} > {})",
+                    self.bench.min_samples, self.bench.max_samples
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the subset of TOML used by `aoc.toml`: tables, comments, and string, integer and boolean values.
+fn parse(contents: &str, file_path: &str) -> Result<Vec<Entry>, ConfigError> {
+    let mut entries: Vec<Entry> = vec![];
+    let mut table = String::new();
+
+    for (idx, raw_line) in contents.lines().enumerate() {
+        let origin = format!("{file_path}:{}", idx + 1);
+        let line = strip_comment(raw_line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').map(str::trim).ok_or(ConfigError {
+                origin: origin.clone(),
+                key: None,
+                message: format!("malformed table header `{line}`"),
+            })?;
+            if !is_bare_key(name) {
+                return Err(ConfigError {
+                    origin,
+                    key: Some(name.into()),
+                    message: "invalid table name".into(),
+                });
+            }
+            table = name.into();
+            continue;
+        }
+
+        let (key, value) = parse_key_value(line, &table, &origin)?;
+
+        if entries.iter().any(|e| e.key == key) {
+            return Err(ConfigError {
+                origin,
+                key: Some(key),
+                message: "key is defined twice".into(),
+            });
+        }
+
+        entries.push(Entry { origin, key, value });
+    }
+
+    Ok(entries)
+}
+
+/// Parses another TOML file in the format of the configuration, e.g. the answers of named inputs.
+/// Returns `(origin, key, value)` for each entry, with keys prefixed by their table (`table.key`)
+/// and values as plain text.
+pub(crate) fn parse_values(
+    contents: &str,
+    file_path: &str,
+) -> Result<Vec<(String, String, String)>, ConfigError> {
+    Ok(parse(contents, file_path)?
+        .into_iter()
+        .map(|entry| {
+            let value = match entry.value {
+                Value::String(s) => s,
+                v => v.to_string(),
+            };
+            (entry.origin, entry.key, value)
+        })
+        .collect())
+}
+
+/// Parses a `--set key=value` override. Values may be quoted, unquoted values are inferred.
+fn parse_override(value: &str) -> Result<Entry, ConfigError> {
+    let origin = format!("--set {value}");
+    let (key, raw) = value.split_once('=').ok_or(ConfigError {
+        origin: origin.clone(),
+        key: None,
+        message: "expected `<key>=<value>`".into(),
+    })?;
+
+    let key = key.trim();
+    if !key.split('.').all(is_bare_key) {
+        return Err(ConfigError {
+            origin,
+            key: Some(key.into()),
+            message: "invalid key".into(),
+        });
+    }
+
+    let raw = raw.trim();
+    let value = parse_value(raw).unwrap_or_else(|_| Value::String(raw.into()));
+
+    Ok(Entry {
+        origin,
+        key: key.into(),
+        value,
+    })
+}
+
+fn parse_key_value(line: &str, table: &str, origin: &str) -> Result<(String, Value), ConfigError> {
+    let error = |key: Option<&str>, message: String| ConfigError {
+        origin: origin.into(),
+        key: key.map(String::from),
+        message,
+    };
+
+    let (key, raw) = line
+        .split_once('=')
+        .ok_or_else(|| error(None, format!("expected `<key> = <value>`, found `{line}`")))?;
+
+    let key = key.trim();
+    if !is_bare_key(key) {
+        return Err(error(Some(key), "invalid key".into()));
+    }
+
+    let key = if table.is_empty() {
+        key.to_string()
+    } else {
+        format!("{table}.{key}")
+    };
+
+    let value = parse_value(raw.trim()).map_err(|e| error(Some(&key), e))?;
+    Ok((key, value))
+}
+
+fn parse_value(raw: &str) -> Result<Value, String> {
+    if let Some(quoted) = raw.strip_prefix('"') {
+        let inner = quoted
+            .strip_suffix('"')
+            .ok_or_else(|| format!("unterminated string `{raw}`"))?;
+        return unescape(inner).map(Value::String);
+    }
+
+    match raw {
+        "true" => return Ok(Value::Boolean(true)),
+        "false" => return Ok(Value::Boolean(false)),
+        _ => {}
+    }
+
+    raw.replace('_', "")
+        .parse::<i64>()
+        .map(Value::Integer)
+        .map_err(|_| format!("expected a string, integer or boolean, found `{raw}`"))
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(c) => return Err(format!("unsupported escape sequence `\\{c}`")),
+                None => return Err("unterminated escape sequence".into()),
+            },
+            '"' => return Err("unescaped quote in string".into()),
+            c => out.push(c),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Removes a trailing `# comment`, ignoring `#` inside of strings.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (idx, ch) in line.char_indices() {
+        match ch {
+            '\\' if in_string => {
+                escaped = !escaped;
+                continue;
+            }
+            '"' if !escaped => in_string = !in_string,
+            '#' if !in_string => return &line[..idx],
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    line
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, parse_override, Config, Level};
+
+    fn load_str(contents: &str, overrides: &[&str]) -> Result<Config, super::ConfigError> {
+        let mut config = Config::default();
+        for entry in parse(contents, "aoc.toml")? {
+            config.apply(&entry)?;
+        }
+        for value in overrides {
+            config.apply(&parse_override(value)?)?;
+        }
+        config.validate("aoc.toml")?;
+        Ok(config)
+    }
+
+    #[test]
+    fn parses_documented_keys() {
+        let config = load_str(
+            r#"
+            # comment
+            [aoc]
+            year = 2023
+
+            [paths]
+            inputs = "inputs/#1" # trailing comment
+            timings = "timings.json"
+
+            [bench]
+            max_samples = 1_000
+            "#,
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.paths.inputs, "inputs/#1");
+        assert_eq!(config.paths.examples, "data/examples");
+        assert_eq!(config.paths.timings, "timings.json");
+        assert_eq!(config.bench.max_samples, 1000);
+        assert!(!config.cli.strict);
+        assert!(config.input.trim_end);
+    }
+
+    #[test]
+    fn defaults_for_empty_file() {
+        assert_eq!(load_str("", &[]).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn overrides_take_precedence() {
+        let config = load_str(
+            "[aoc]\nyear = 2023\n",
+            &[
+                "aoc.year=2022",
+                "readme.marker=<!-- x -->",
+                "cli.strict=true",
+                "trace.level=trace",
+                "viz.fps=30",
+                "serve.workers=2",
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.year, Some(2022));
+        assert!(config.cli.strict);
+        assert_eq!(config.trace.level, Level::Trace);
+        assert_eq!(config.viz.fps, 30);
+        assert_eq!(config.serve.workers, 2);
+        assert_eq!(config.readme.marker, "<!-- x -->");
+    }
+
+    #[test]
+    fn errors_point_at_key() {
+        let err = load_str("[bench]\n\nmin_samples = \"ten\"\n", &[]).unwrap_err();
+        assert_eq!(err.origin, "aoc.toml:3");
+        assert_eq!(err.key.as_deref(), Some("bench.min_samples"));
+        assert_eq!(
+            err.to_string(),
+            "aoc.toml:3: `bench.min_samples`: expected a positive integer, found `\"ten\"`"
+        );
+    }
+
+    #[test]
+    fn errors_for_unknown_keys() {
+        let err = load_str("[paths]\ninput = \"x\"\n", &[]).unwrap_err();
+        assert_eq!(err.key.as_deref(), Some("paths.input"));
+        assert_eq!(err.message, "unknown key");
+
+        let err = load_str("[trace]\nlevel = \"loud\"\n", &[]).unwrap_err();
+        assert_eq!(err.key.as_deref(), Some("trace.level"));
+
+        let err = load_str("", &["paths.input=x"]).unwrap_err();
+        assert_eq!(err.origin, "--set paths.input=x");
+    }
+
+    #[test]
+    fn errors_for_inconsistent_values() {
+        let err = load_str("[bench]\nmin_samples = 20\nmax_samples = 10\n", &[]).unwrap_err();
+        assert_eq!(err.key.as_deref(), Some("bench.min_samples"));
+
+        let config = load_str("[bench]\nmin_samples = 20000\nmax_samples = 30000\n", &[]).unwrap();
+        assert_eq!(config.bench.min_samples, 20000);
+    }
+
+    #[test]
+    fn errors_for_malformed_lines() {
+        assert!(load_str("[paths\n", &[]).is_err());
+        assert!(load_str("year 2023\n", &[]).is_err());
+        assert!(load_str("[aoc]\nyear = 2023\nyear = 2024\n", &[]).is_err());
+        assert!(load_str("[paths]\ninputs = \"x\n", &[]).is_err());
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
@@ -47,14 +47,39 @@ impl Day {
 impl Day {
     /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
     pub fn today() -> Option<Self> {
+        Self::today_at(Utc::now())
+    }
+
+    /// Returns the day whose puzzle is the most recent one unlocked at `now`, if it's in december.
+    pub fn today_at(now: DateTime<Utc>) -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
+        let today = now.with_timezone(&offset);
         if today.month() == 12 && today.day() <= 25 {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the instant the puzzle for this day unlocks in `year`: midnight on the server.
+    pub fn unlock_time(self, year: i32) -> Option<DateTime<Utc>> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        offset
+            .with_ymd_and_hms(year, 12, u32::from(self.0), 0, 0, 0)
+            .single()
+            .map(|time| time.with_timezone(&Utc))
+    }
+
+    /// Returns the next puzzle to unlock strictly after `now` together with its unlock time.
+    /// Returns `None` once the last puzzle of the year has been unlocked.
+    pub fn next_unlock(now: DateTime<Utc>) -> Option<(Self, DateTime<Utc>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let year = now.with_timezone(&offset).year();
+
+        all_days()
+            .filter_map(|day| Some((day, day.unlock_time(year)?)))
+            .find(|(_, unlock)| *unlock > now)
+    }
 }
 
 impl Display for Day {
@@ -156,6 +181,28 @@ macro_rules! day {
 mod tests {
     use super::{all_days, Day};
 
+    #[cfg(feature = "today")]
+    #[test]
+    fn computes_unlock_times() {
+        use chrono::{TimeZone, Utc};
+
+        let unlock = Day(5).unlock_time(2024).unwrap();
+        assert_eq!(unlock, Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap());
+
+        let before = Utc.with_ymd_and_hms(2024, 12, 5, 4, 59, 59).unwrap();
+        assert_eq!(Day::today_at(before), Some(Day(4)));
+        assert_eq!(Day::next_unlock(before), Some((Day(5), unlock)));
+        assert_eq!(Day::today_at(unlock), Some(Day(5)));
+        assert_eq!(Day::next_unlock(unlock).unwrap().0, Day(6));
+
+        let november = Utc.with_ymd_and_hms(2024, 11, 30, 12, 0, 0).unwrap();
+        assert_eq!(Day::today_at(november), None);
+        assert_eq!(Day::next_unlock(november).unwrap().0, Day(1));
+
+        let after = Utc.with_ymd_and_hms(2024, 12, 25, 5, 0, 0).unwrap();
+        assert_eq!(Day::next_unlock(after), None);
+    }
+
     #[test]
     fn all_days_iterator() {
         let mut iter = all_days();
@@ -0,0 +1,260 @@
+use std::fmt::Display;
+use std::io;
+use std::process::ExitStatus;
+
+use crate::template::aoc_cli::SubmitOutcome;
+use crate::template::config::ConfigError;
+use crate::template::{Day, DayFromStrError, PartFromStrError};
+
+/// Exit codes the template exits with, one per kind of [`Error`].
+pub mod exit_code {
+    pub const USAGE: i32 = 2;
+    pub const MISSING_TOOL: i32 = 3;
+    pub const SOLUTION: i32 = 4;
+    pub const IO: i32 = 5;
+    pub const CONFIG: i32 = 6;
+    pub const COMMAND: i32 = 7;
+    pub const DATA: i32 = 8;
+    pub const SUBMISSION: i32 = 9;
+
+    pub const ALL: [i32; 8] = [
+        USAGE,
+        MISSING_TOOL,
+        SOLUTION,
+        IO,
+        CONFIG,
+        COMMAND,
+        DATA,
+        SUBMISSION,
+    ];
+}
+
+/// An error raised by the template.
+///
+/// Errors can be wrapped with [`ErrorContext::context`] to explain what was being attempted.
+/// The process exit code is derived from the innermost error, see [`Error::exit_code`].
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line input.
+    Usage(String),
+    /// Invalid `aoc.toml` or `--set` value.
+    Config(ConfigError),
+    /// A required external tool is not installed or not callable.
+    MissingTool {
+        tool: &'static str,
+        hint: &'static str,
+    },
+    /// An external command exited with a non-zero status.
+    Command { command: String, status: ExitStatus },
+    /// Advent of Code did not accept a submission.
+    Submission(SubmitOutcome),
+    /// A solution failed to run or did not produce an answer.
+    Solution(String),
+    /// A solution binary reported a template error itself and exited with `code`, which is forwarded.
+    Child { message: String, code: i32 },
+    /// Reading or writing a file or pipe failed.
+    Io(io::Error),
+    /// A file the template maintains (e.g. the readme or timings) has unexpected contents.
+    Data(String),
+    /// Describes what was attempted when `source` occurred.
+    Context { context: String, source: Box<Error> },
+}
+
+impl Error {
+    /// The process exit code for this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => exit_code::USAGE,
+            Error::Config(_) => exit_code::CONFIG,
+            Error::MissingTool { .. } => exit_code::MISSING_TOOL,
+            Error::Command { .. } => exit_code::COMMAND,
+            Error::Submission(_) => exit_code::SUBMISSION,
+            Error::Solution(_) => exit_code::SOLUTION,
+            Error::Child { code, .. } => *code,
+            Error::Io(_) => exit_code::IO,
+            Error::Data(_) => exit_code::DATA,
+            Error::Context { source, .. } => source.exit_code(),
+        }
+    }
+
+    /// Iterates over this error and all errors it wraps, outermost first.
+    pub fn chain(&self) -> impl Iterator<Item = &Error> {
+        std::iter::successors(Some(self), |err| match err {
+            Error::Context { source, .. } => Some(source),
+            _ => None,
+        })
+    }
+
+    /// Maps a failure to spawn `cargo` to [`Error::MissingTool`] if it is not installed.
+    pub(crate) fn from_cargo_spawn(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => Error::MissingTool {
+                tool: "cargo",
+                hint: "Install the Rust toolchain from https://rustup.rs.",
+            },
+            _ => Error::Io(e),
+        }
+    }
+
+    /// Creates the error for a solution binary that exited with `status`.
+    pub(crate) fn from_solution_status(day: Day, status: ExitStatus) -> Self {
+        let message = format!("The solution for day {day} failed ({status}).");
+        match status.code() {
+            Some(code) if exit_code::ALL.contains(&code) => Error::Child { message, code },
+            _ => Error::Solution(message),
+        }
+    }
+
+    /// Prints the error and its causes to stderr and exits with the matching exit code.
+    pub fn exit(&self) -> ! {
+        let mut chain = self.chain();
+        if let Some(err) = chain.next() {
+            eprintln!("Error: {err}");
+        }
+        for cause in chain {
+            eprintln!("  caused by: {cause}");
+        }
+        std::process::exit(self.exit_code())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) => f.write_str(message),
+            Error::Config(e) => write!(f, "invalid configuration: {e}"),
+            Error::MissingTool { tool, hint } => {
+                write!(f, "command \"{tool}\" not found or not callable. {hint}")
+            }
+            Error::Command { command, status } => write!(f, "{command} exited with {status}"),
+            Error::Submission(outcome) => write!(f, "{outcome}"),
+            Error::Solution(message) | Error::Child { message, .. } => f.write_str(message),
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Data(message) => f.write_str(message),
+            Error::Context { context, .. } => f.write_str(context),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(e: ConfigError) -> Self {
+        Error::Config(e)
+    }
+}
+
+impl From<DayFromStrError> for Error {
+    fn from(e: DayFromStrError) -> Self {
+        Error::Usage(e.to_string())
+    }
+}
+
+impl From<PartFromStrError> for Error {
+    fn from(e: PartFromStrError) -> Self {
+        Error::Usage(e.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extension trait to attach context to fallible results.
+pub trait ErrorContext<T> {
+    /// Wraps the error with a message describing what was attempted.
+    fn context(self, context: impl Into<String>) -> Result<T, Error>;
+
+    /// Like [`ErrorContext::context`], but builds the message lazily.
+    fn with_context<S: Into<String>>(self, f: impl FnOnce() -> S) -> Result<T, Error>;
+}
+
+impl<T, E: Into<Error>> ErrorContext<T> for Result<T, E> {
+    fn context(self, context: impl Into<String>) -> Result<T, Error> {
+        self.with_context(|| context)
+    }
+
+    fn with_context<S: Into<String>>(self, f: impl FnOnce() -> S) -> Result<T, Error> {
+        self.map_err(|e| Error::Context {
+            context: f().into(),
+            source: Box::new(e.into()),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{exit_code, Error, ErrorContext};
+    use crate::template::aoc_cli::SubmitOutcome;
+    use crate::template::config::ConfigError;
+    use std::io;
+    #[cfg(unix)]
+    use std::os::unix::process::ExitStatusExt;
+    #[cfg(windows)]
+    use std::os::windows::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    #[test]
+    fn exit_code_of_innermost_error() {
+        let err: Result<(), Error> = Err(io::Error::other("disk full"))
+            .context("could not write timings")
+            .context("failed to store benchmarks");
+
+        let err = err.unwrap_err();
+        assert_eq!(err.exit_code(), exit_code::IO);
+        assert_eq!(err.to_string(), "failed to store benchmarks");
+
+        let messages: Vec<_> = err.chain().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "failed to store benchmarks",
+                "could not write timings",
+                "disk full"
+            ]
+        );
+    }
+
+    #[test]
+    fn distinct_exit_codes() {
+        let codes = [
+            Error::Usage(String::new()).exit_code(),
+            Error::Config(ConfigError {
+                origin: String::new(),
+                key: None,
+                message: String::new(),
+            })
+            .exit_code(),
+            Error::MissingTool {
+                tool: "aoc",
+                hint: "",
+            }
+            .exit_code(),
+            Error::Command {
+                command: String::new(),
+                status: ExitStatus::from_raw(1),
+            }
+            .exit_code(),
+            Error::Submission(SubmitOutcome::Correct).exit_code(),
+            Error::Solution(String::new()).exit_code(),
+            Error::Io(io::Error::other("")).exit_code(),
+            Error::Data(String::new()).exit_code(),
+        ];
+        for (i, a) in codes.iter().enumerate() {
+            assert!(codes[i + 1..].iter().all(|b| a != b));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Normalization of input files before they are passed to a solution.
+//!
+//! Inputs saved on Windows or copied from a browser can differ from the downloaded file in
+//! invisible ways: `\r\n` line endings, a byte order mark or extra blank lines at the end.
+//! The steps applied are configured in the `[input]` table of `aoc.toml`.
+use std::{borrow::Cow, fmt::Display, fs, io, path::Path};
+
+use crate::template::config;
+
+/// A change made to an input by [`normalize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// A leading byte order mark was removed.
+    ByteOrderMark,
+    /// This many `\r\n` line endings were converted to `\n`.
+    LineEndings(usize),
+    /// Whitespace at the end of the input was removed.
+    TrailingWhitespace(String),
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::ByteOrderMark => f.write_str("removed a byte order mark"),
+            Change::LineEndings(1) => f.write_str("converted 1 CRLF line ending"),
+            Change::LineEndings(n) => write!(f, "converted {n} CRLF line endings"),
+            Change::TrailingWhitespace(ws) => write!(f, "trimmed trailing whitespace {ws:?}"),
+        }
+    }
+}
+
+impl Change {
+    /// Whether the change is worth a warning. Downloaded inputs end with a single newline,
+    /// removing it is expected.
+    pub fn is_notable(&self) -> bool {
+        !matches!(self, Change::TrailingWhitespace(ws) if ws == "\n")
+    }
+}
+
+/// Applies the normalization steps enabled in `options` to `raw`.
+pub fn normalize<'a>(raw: &'a str, options: &config::Input) -> (Cow<'a, str>, Vec<Change>) {
+    let mut text = Cow::Borrowed(raw);
+    let mut changes = vec![];
+
+    if options.strip_bom {
+        if let Some(rest) = text.strip_prefix('\u{feff}') {
+            text = Cow::Owned(rest.to_string());
+            changes.push(Change::ByteOrderMark);
+        }
+    }
+
+    if options.line_endings {
+        let count = text.matches("\r\n").count();
+        if count > 0 {
+            text = Cow::Owned(text.replace("\r\n", "\n"));
+            changes.push(Change::LineEndings(count));
+        }
+    }
+
+    if options.trim_end {
+        let len = text.trim_end().len();
+        if len < text.len() {
+            changes.push(Change::TrailingWhitespace(text[len..].to_string()));
+            match &mut text {
+                Cow::Borrowed(s) => *s = &s[..len],
+                Cow::Owned(s) => s.truncate(len),
+            }
+        }
+    }
+
+    (text, changes)
+}
+
+/// Reads the file at `path` and normalizes it, warning about notable changes on stderr.
+pub fn read(path: &Path) -> io::Result<String> {
+    let raw = fs::read_to_string(path)?;
+    let (text, changes) = normalize(&raw, &config::get().input);
+
+    let notable: Vec<String> = changes
+        .iter()
+        .filter(|change| change.is_notable())
+        .map(Change::to_string)
+        .collect();
+    if !notable.is_empty() {
+        eprintln!(
+            "Warning: normalized \"{}\": {}.",
+            path.display(),
+            notable.join(", ")
+        );
+    }
+
+    Ok(text.into_owned())
+}
+
+/// Reads the file at `path` without any normalization.
+pub fn read_raw(path: &Path) -> io::Result<Vec<u8>> {
+    fs::read(path)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{normalize, Change};
+    use crate::template::config;
+
+    const ALL: config::Input = config::Input {
+        strip_bom: true,
+        line_endings: true,
+        trim_end: true,
+    };
+
+    #[test]
+    fn normalizes_windows_files() {
+        let (text, changes) = normalize("\u{feff}12345\r\n\r\nab\r\n\r\n", &ALL);
+        assert_eq!(text, "12345\n\nab");
+        assert_eq!(
+            changes,
+            [
+                Change::ByteOrderMark,
+                Change::LineEndings(4),
+                Change::TrailingWhitespace("\n\n".into())
+            ]
+        );
+        assert!(changes.iter().all(Change::is_notable));
+    }
+
+    #[test]
+    fn trims_downloaded_files_quietly() {
+        let (text, changes) = normalize("2333133121414131402\n", &ALL);
+        assert!(matches!(text, Cow::Borrowed("2333133121414131402")));
+        assert_eq!(changes, [Change::TrailingWhitespace("\n".into())]);
+        assert!(!changes[0].is_notable());
+    }
+
+    #[test]
+    fn respects_options() {
+        let none = config::Input {
+            strip_bom: false,
+            line_endings: false,
+            trim_end: false,
+        };
+        let raw = "\u{feff}a\r\nb\r\n";
+        assert_eq!(normalize(raw, &none), (Cow::Borrowed(raw), vec![]));
+
+        let endings_only = config::Input {
+            line_endings: true,
+            ..none
+        };
+        assert_eq!(normalize(raw, &endings_only).0, "\u{feff}a\nb\n");
+    }
+}
@@ -0,0 +1,27 @@
+//! Inspection commands that a day offers in `cargo repl`, e.g. to print its grid after a few
+//! steps instead of adding `println!`s to the solution.
+//!
+//! A day implements [`Inspect`] for a type holding the state it wants to explore and registers
+//! it with its solution:
+//!
+//! ```ignore
+//! advent_of_code::solution!(15; inspect: Inspector);
+//! ```
+
+/// State of a day that can be explored from the REPL.
+pub trait Inspect {
+    /// Creates the state for `input`. Called again whenever the REPL switches inputs.
+    fn new(input: &str) -> Self
+    where
+        Self: Sized;
+
+    /// The commands understood by [`Inspect::run`], as usage and help,
+    /// e.g. `("step [n]", "Moves the robot n times.")`.
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Runs one of the [`Inspect::commands`] and returns the text to print, or what went wrong.
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+/// Creates the inspection state of a day for an input, see `solution!`.
+pub type Factory = fn(&str) -> Box<dyn Inspect>;
@@ -0,0 +1,469 @@
+//! Renders the markdown puzzle descriptions written by aoc-cli for the terminal.
+//! Only the subset of markdown that appears in puzzle descriptions is supported.
+use crate::template::{output, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Style of emphasized spans that look like answers, e.g. `*42*`: bold yellow.
+const ANSI_ANSWER: &str = "\x1b[1;33m";
+/// Style of the separator between parts: dim.
+const ANSI_DIM: &str = "\x1b[2m";
+
+/// Terminals wider than this still wrap here, long lines are hard to read.
+const MAX_WIDTH: usize = 100;
+/// Width assumed if the terminal doesn't tell.
+const DEFAULT_WIDTH: usize = 80;
+
+/// How to render markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Use ANSI styles, box-drawing characters and a part separator.
+    pub ansi: bool,
+    /// Wrap paragraphs at this many columns.
+    pub width: Option<usize>,
+}
+
+impl Options {
+    /// Options for stdout: styled if [`output::color`] allows it, wrapped if it is interactive.
+    pub fn terminal() -> Self {
+        Self {
+            ansi: output::color(),
+            width: output::interactive().then(terminal_width),
+        }
+    }
+
+    /// Text without markup, styles or wrapping.
+    pub fn plain() -> Self {
+        Self {
+            ansi: false,
+            width: None,
+        }
+    }
+}
+
+fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+        .min(MAX_WIDTH)
+}
+
+/// Renders `markdown`. With ANSI styles, headings and emphasis are bold, inline code italic,
+/// answer-like emphasis highlighted and code blocks boxed.
+pub fn render(markdown: &str, options: &Options) -> String {
+    let mut out = String::new();
+    for block in blocks(markdown) {
+        match block {
+            Block::Blank => out.push('\n'),
+            Block::Heading(text) => {
+                let text = inline(text, false);
+                if options.ansi {
+                    out.push_str(&format!("{ANSI_BOLD}{text}{ANSI_RESET}\n"));
+                } else {
+                    out.push_str(&format!("{text}\n"));
+                }
+            }
+            Block::Paragraph(lines) => {
+                let text = inline(&lines.join(" "), options.ansi);
+                out.push_str(&wrap(&text, options.width, "", ""));
+            }
+            Block::ListItem(text) => {
+                let text = inline(text, options.ansi);
+                out.push_str(&wrap(&text, options.width, "  • ", "    "));
+            }
+            Block::Code(lines) if options.ansi => out.push_str(&boxed(&lines)),
+            Block::Code(lines) => {
+                for line in lines {
+                    out.push_str(&format!("    {line}\n"));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Returns the text of `markdown` without any markup, e.g. for searching.
+pub fn plain(markdown: &str) -> String {
+    render(markdown, &Options::plain())
+}
+
+/// A line that separates the parts of a puzzle, empty without ANSI styles.
+pub fn separator(options: &Options) -> String {
+    if options.ansi {
+        let width = options.width.unwrap_or(DEFAULT_WIDTH);
+        format!("{ANSI_DIM}{}{ANSI_RESET}\n\n", "─".repeat(width))
+    } else {
+        String::new()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+enum Block<'a> {
+    Blank,
+    Heading(&'a str),
+    Paragraph(Vec<&'a str>),
+    ListItem(&'a str),
+    Code(Vec<&'a str>),
+}
+
+fn blocks(markdown: &str) -> Vec<Block<'_>> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut blocks = vec![];
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+
+        if line.trim_start().starts_with("```") {
+            let start = i;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                i += 1;
+            }
+            blocks.push(Block::Code(lines[start..i].to_vec()));
+            // NOTE: skip the closing fence.
+            i += 1;
+        } else if line.trim().is_empty() {
+            blocks.push(Block::Blank);
+        } else if let Some(text) = heading(line, lines.get(i).copied()) {
+            if !is_atx_heading(line) {
+                // NOTE: skip the setext underline.
+                i += 1;
+            }
+            blocks.push(Block::Heading(text));
+        } else if let Some(item) = list_item(line) {
+            blocks.push(Block::ListItem(item));
+        } else {
+            match blocks.last_mut() {
+                Some(Block::Paragraph(lines)) => lines.push(line.trim()),
+                _ => blocks.push(Block::Paragraph(vec![line.trim()])),
+            }
+        }
+    }
+
+    blocks
+}
+
+fn is_atx_heading(line: &str) -> bool {
+    line.starts_with('#')
+}
+
+fn is_setext_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+/// Returns the text of `line` if it is a heading, either `## text` or text underlined by `next`.
+fn heading<'a>(line: &'a str, next: Option<&str>) -> Option<&'a str> {
+    if is_atx_heading(line) {
+        return Some(line.trim_start_matches('#').trim());
+    }
+    match next {
+        Some(next) if !line.trim().is_empty() && is_setext_underline(next) => Some(line.trim()),
+        _ => None,
+    }
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    line.strip_prefix("* ")
+        .or_else(|| line.strip_prefix("- "))
+        .or_else(|| line.strip_prefix("+ "))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Currently open inline spans.
+#[derive(Default, Clone, Copy)]
+struct Spans {
+    bold: bool,
+    code: bool,
+    answer_bold: bool,
+    answer_code: bool,
+}
+
+impl Spans {
+    fn style(self) -> String {
+        let mut style = ANSI_RESET.to_string();
+        if self.answer_bold || self.answer_code {
+            style.push_str(ANSI_ANSWER);
+        } else if self.bold {
+            style.push_str(ANSI_BOLD);
+        }
+        if self.code {
+            style.push_str(ANSI_ITALIC);
+        }
+        style
+    }
+}
+
+/// Renders emphasis, inline code, links and escapes of a single line.
+fn inline(line: &str, styled: bool) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+    let mut spans = Spans::default();
+    // NOTE: a link `[text](url)` renders as its text; this tracks where to skip the url.
+    let mut link_close = None;
+    let mut i = 0;
+
+    let restyle = |out: &mut String, spans: Spans| {
+        if styled {
+            out.push_str(&spans.style());
+        }
+    };
+
+    while i < chars.len() {
+        if let Some((close, end)) = link_close {
+            if i == close {
+                link_close = None;
+                i = end + 1;
+                continue;
+            }
+        }
+
+        match chars[i] {
+            '\\' if chars.get(i + 1).is_some_and(char::is_ascii_punctuation) => {
+                out.push(chars[i + 1]);
+                i += 2;
+            }
+            '`' => {
+                spans.code = !spans.code;
+                spans.answer_code = spans.code
+                    && (i + 1..chars.len())
+                        .find(|&j| chars[j] == '`')
+                        .is_some_and(|j| is_answer(&chars[i + 1..j]));
+                restyle(&mut out, spans);
+                i += 1;
+            }
+            '*' | '_' if spans.bold || closing_emphasis(&chars, i).is_some() => {
+                let run = emphasis_run(&chars, i);
+                if spans.bold {
+                    if !closes_emphasis(&chars, i) {
+                        out.push(chars[i]);
+                        i += 1;
+                        continue;
+                    }
+                    spans.bold = false;
+                    spans.answer_bold = false;
+                } else {
+                    let close = closing_emphasis(&chars, i).unwrap_or(chars.len());
+                    spans.bold = true;
+                    spans.answer_bold = is_answer(&chars[i + run..close]);
+                }
+                restyle(&mut out, spans);
+                i += run;
+            }
+            '[' if link_close.is_none() => {
+                match find_link(&chars, i) {
+                    Some(link) => link_close = Some(link),
+                    None => out.push('['),
+                }
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if styled && (spans.bold || spans.code) {
+        out.push_str(ANSI_RESET);
+    }
+    out
+}
+
+fn emphasis_run(chars: &[char], i: usize) -> usize {
+    chars[i..]
+        .iter()
+        .take(2)
+        .take_while(|c| **c == chars[i])
+        .count()
+}
+
+/// Checks whether the delimiter at `i` can close emphasis.
+fn closes_emphasis(chars: &[char], i: usize) -> bool {
+    i.checked_sub(1).is_some_and(|j| !chars[j].is_whitespace())
+}
+
+/// Returns the position of the delimiter closing emphasis opened at `i`, if there is one.
+fn closing_emphasis(chars: &[char], i: usize) -> Option<usize> {
+    let delim = chars[i];
+    let run = emphasis_run(chars, i);
+
+    // NOTE: `_` only emphasizes on word boundaries, so identifiers like `a_b` stay intact.
+    if delim == '_' && i.checked_sub(1).is_some_and(|j| chars[j].is_alphanumeric()) {
+        return None;
+    }
+    if chars.get(i + run).is_none_or(|c| c.is_whitespace()) {
+        return None;
+    }
+
+    (i + run + 1..chars.len()).find(|&j| chars[j] == delim && closes_emphasis(chars, j))
+}
+
+/// Checks whether the text of a span is a number, like most puzzle answers.
+fn is_answer(span: &[char]) -> bool {
+    let digits: Vec<char> = span
+        .iter()
+        .copied()
+        .filter(|c| !matches!(c, '*' | '_' | '`' | ','))
+        .collect();
+    let digits = digits.strip_prefix(&['-']).unwrap_or(&digits);
+    !digits.is_empty() && digits.iter().all(char::is_ascii_digit)
+}
+
+/// Returns the positions of `]` and `)` if a link `[text](url)` starts at `i`.
+fn find_link(chars: &[char], i: usize) -> Option<(usize, usize)> {
+    let close = (i + 1..chars.len()).find(|&j| chars[j] == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = (close + 2..chars.len()).find(|&j| chars[j] == ')')?;
+    Some((close, end))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of columns `text` takes up in a terminal, ignoring ANSI escape sequences.
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+/// Wraps `text` at `width` columns. The first line starts with `first`, the others with `rest`.
+fn wrap(text: &str, width: Option<usize>, first: &str, rest: &str) -> String {
+    let Some(width) = width else {
+        return format!("{first}{text}\n");
+    };
+
+    let mut out = String::from(first);
+    let mut column = visible_width(first);
+
+    // NOTE: splitting at single spaces keeps runs of spaces, e.g. in inline code.
+    for (i, word) in text.split(' ').enumerate() {
+        let word_width = visible_width(word);
+        if i > 0 && column + 1 + word_width > width {
+            out.push('\n');
+            out.push_str(rest);
+            column = visible_width(rest);
+        } else if i > 0 {
+            out.push(' ');
+            column += 1;
+        }
+        out.push_str(word);
+        column += word_width;
+    }
+
+    out.push('\n');
+    out
+}
+
+/// Draws a box around the lines of a code block.
+fn boxed(lines: &[&str]) -> String {
+    let width = lines
+        .iter()
+        .map(|line| visible_width(line))
+        .max()
+        .unwrap_or(0);
+    let border = "─".repeat(width + 2);
+
+    let mut out = format!("┌{border}┐\n");
+    for line in lines {
+        let padding = width - visible_width(line);
+        out.push_str(&format!("│ {line}{:padding$} │\n", ""));
+    }
+    out.push_str(&format!("└{border}┘\n"));
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{plain, render, visible_width, Options, ANSI_ANSWER};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const ANSI: Options = Options {
+        ansi: true,
+        width: None,
+    };
+
+    #[test]
+    fn renders_headings() {
+        let md = "\\--- Day 1: Historian Hysteria ---\n----------\n\nText\n\n## Part Two\n";
+        assert_eq!(
+            render(md, &ANSI),
+            format!(
+                "{ANSI_BOLD}--- Day 1: Historian Hysteria ---{ANSI_RESET}\n\nText\n\n{ANSI_BOLD}Part Two{ANSI_RESET}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn renders_inline_styles() {
+        assert_eq!(
+            render("The *Chief Historian* uses `3   4`.", &ANSI),
+            format!("The {ANSI_RESET}{ANSI_BOLD}Chief Historian{ANSI_RESET} uses {ANSI_RESET}{ANSI_ITALIC}3   4{ANSI_RESET}.\n")
+        );
+        assert_eq!(plain("a **b** and `*c*`"), "a b and c\n");
+    }
+
+    #[test]
+    fn highlights_answers() {
+        assert_eq!(
+            render("a total of *11*.", &ANSI),
+            format!("a total of {ANSI_RESET}{ANSI_ANSWER}11{ANSI_RESET}.\n")
+        );
+        assert!(render("answer was `*1,234*`", &ANSI)
+            .contains(&format!("{ANSI_ANSWER}{ANSI_ITALIC}1,234")));
+        assert!(!render("the *tallest* tree", &ANSI).contains(ANSI_ANSWER));
+    }
+
+    #[test]
+    fn keeps_literal_characters() {
+        assert_eq!(
+            plain("2 * 3 = 6, a_b_c, \\*x\\*"),
+            "2 * 3 = 6, a_b_c, *x*\n"
+        );
+        assert_eq!(
+            plain("see [the docs](https://example.com) [sic]"),
+            "see the docs [sic]\n"
+        );
+    }
+
+    #[test]
+    fn renders_blocks() {
+        let md = "* one\n* two\n\n```\n3   4\n*\n```\n";
+        assert_eq!(plain(md), "  • one\n  • two\n\n    3   4\n    *\n");
+
+        let boxed = render("```\n3   4\n10\n```\n", &ANSI);
+        assert_eq!(boxed, "┌───────┐\n│ 3   4 │\n│ 10    │\n└───────┘\n");
+    }
+
+    #[test]
+    fn wraps_paragraphs() {
+        let options = Options {
+            ansi: true,
+            width: Some(20),
+        };
+        let md =
+            "The quick brown fox\njumps over the *lazy* dog.\n\n* a list item that wraps around\n";
+        let rendered = render(md, &options);
+
+        let lines: Vec<_> = rendered.lines().collect();
+        assert!(lines.iter().all(|line| visible_width(line) <= 20));
+        assert_eq!(plain(md).lines().count(), 3);
+        assert_eq!(lines[0], "The quick brown fox");
+        assert!(lines.last().unwrap().starts_with("    "));
+    }
+}
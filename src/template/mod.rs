@@ -1,12 +1,27 @@
-use std::{env, fs};
+use std::env;
 
 pub mod aoc_cli;
+pub mod cli;
+#[cfg(feature = "today")]
+pub mod clock;
 pub mod commands;
+pub mod config;
+pub mod input;
+pub mod inspect;
+pub mod markdown;
+pub mod named_inputs;
+pub mod ocr;
+pub mod output;
+pub mod puzzles;
 pub mod runner;
 
 pub use day::*;
+pub use error::{exit_code, Error, ErrorContext};
+pub use part::*;
 
 mod day;
+mod error;
+mod part;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
@@ -15,54 +30,135 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string, normalized as configured in `[input]`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let filepath = cwd
+        .join(config::get().paths.folder(folder))
+        .join(format!("{day}.txt"));
+    let f = input::read(&filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, day: Day, part: Part) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
+        .join(config::get().paths.folder(folder))
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = input::read(&filepath);
+    f.expect("could not open input file")
+}
+
+/// Helper function that reads a named input of a day, e.g. `data/inputs/01/alice.txt`.
+#[must_use]
+pub fn read_file_named(folder: &str, day: Day, name: &str) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join(config::get().paths.folder(folder))
+        .join(day.to_string())
+        .join(format!("{name}.txt"));
+    let f = input::read(&filepath);
+    f.expect("could not open input file")
+}
+
+/// Helper function that reads a text file as is, without normalizing it.
+#[must_use]
+pub fn read_file_raw(folder: &str, day: Day) -> Vec<u8> {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join(config::get().paths.folder(folder))
+        .join(format!("{day}.txt"));
+    let f = input::read_raw(&filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternative implementations can be added as named variants, each listing its functions and the
+/// part they solve. They are run with `--variant <name>` and benched with `cargo time --variants`:
+///
+/// ```ignore
+/// advent_of_code::solution!(9, naive: [part_one_naive, 1]);
+/// ```
+///
+/// A type implementing [`inspect::Inspect`] can be registered last, to offer its commands in
+/// `cargo repl`:
+///
+/// ```ignore
+/// advent_of_code::solution!(15; inspect: Inspector);
+/// ```
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, $variant:ident: $( [$vfunc:expr, $vpart:expr] )+ )* $(; inspect: $inspect:ty)? $(,)?) => {
+        $crate::solution!(@impl $day, [$($inspect)?] [part_one, 1] [part_two, 2] $(; $variant: $( [$vfunc, $vpart] )+ )*);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 1 $(, $variant:ident: $( [$vfunc:expr, $vpart:expr] )+ )* $(; inspect: $inspect:ty)? $(,)?) => {
+        $crate::solution!(@impl $day, [$($inspect)?] [part_one, 1] $(; $variant: $( [$vfunc, $vpart] )+ )*);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, 2 $(, $variant:ident: $( [$vfunc:expr, $vpart:expr] )+ )* $(; inspect: $inspect:ty)? $(,)?) => {
+        $crate::solution!(@impl $day, [$($inspect)?] [part_two, 2] $(; $variant: $( [$vfunc, $vpart] )+ )*);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@inspect) => {
+        None
+    };
+    (@inspect $inspect:ty) => {
+        Some(|input| Box::new(<$inspect as $crate::template::inspect::Inspect>::new(input)))
+    };
+
+    (@impl $day:expr, [$($inspect:ty)?] $( [$func:expr, $part:expr] )* $(; $variant:ident: $( [$vfunc:expr, $vpart:expr] )+ )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// Entry points of the day for the library, see `advent_of_code::solve`.
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub(crate) mod __solution {
+            use super::*;
+
+            pub const DAY: $crate::template::Day = super::DAY;
+            pub const PARTS: &[$crate::template::Part] = &[$( $crate::part!($part) ),*];
+            pub const INSPECT: Option<$crate::template::inspect::Factory> =
+                $crate::solution!(@inspect $($inspect)?);
+
+            pub fn solve(part: $crate::template::Part, input: &str) -> Option<Option<String>> {
+                $(
+                    if part == $crate::part!($part) {
+                        return Some($func(input).map(|answer| answer.to_string()));
+                    }
+                )*
+                None
+            }
+        }
 
+        // NOTE: unused when the day is compiled into the library.
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let args = RunnerArgs::from_env();
+            // NOTE: unused if the solution has no variants.
+            #[allow(unused_variables)]
+            let variant = select_variant(&args, &[$( stringify!($variant) ),*]);
+            init_trace(DAY, &args);
+            init_viz(&args);
+            $(
+                if variant == stringify!($variant) {
+                    check_submit(&args, &[$( $crate::part!($vpart) ),+]);
+                    let input = read_input(DAY, args.input.as_deref());
+                    $( run_part($vfunc, &input, DAY, $crate::part!($vpart), &args); )+
+                    finish_viz();
+                    return;
+                }
+            )*
+            check_submit(&args, &[$( $crate::part!($part) ),*]);
+            let input = read_input(DAY, args.input.as_deref());
+            $( run_part($func, &input, DAY, $crate::part!($part), &args); )*
+            finish_viz();
         }
     };
 }
@@ -0,0 +1,137 @@
+//! Named inputs: several puzzle inputs per day, e.g. pooled from a team, stored as
+//! `{paths.inputs}/{day}/{name}.txt`.
+//!
+//! Their known answers are recorded next to them in `answers.toml`, one table per input:
+//!
+//! ```toml
+//! [alice]
+//! part_1 = 11
+//! part_2 = "31"
+//! ```
+use std::{collections::HashMap, fs, io, path::PathBuf};
+
+use crate::template::config::{self, ConfigError};
+use crate::template::{Day, Error, ErrorContext, Part};
+
+/// Name of the file holding the answers of a day's named inputs.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// A named input of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedInput {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Returns the folder holding the named inputs of `day`.
+pub fn folder(day: Day) -> PathBuf {
+    config::get().paths.folder("inputs").join(day.to_string())
+}
+
+/// Lists the named inputs of `day`, sorted by name. Empty if the day has none.
+pub fn list(day: Day) -> Result<Vec<NamedInput>, Error> {
+    let folder = folder(day);
+    let entries = match fs::read_dir(&folder) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => {
+            return Err(e).with_context(|| format!("could not list \"{}\"", folder.display()))
+        }
+    };
+
+    let mut inputs = vec![];
+    for entry in entries {
+        let path = entry.context("could not list named inputs")?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                inputs.push(NamedInput {
+                    name: name.to_string(),
+                    path: path.clone(),
+                });
+            }
+        }
+    }
+
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(inputs)
+}
+
+/// Recorded answers of a day's named inputs.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(String, Part), String>,
+}
+
+impl Answers {
+    /// Reads the answers of `day`. Empty if the day has no answers file.
+    pub fn read(day: Day) -> Result<Self, Error> {
+        let path = folder(day).join(ANSWERS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(contents) => Ok(Self::parse(&contents, &path.to_string_lossy())?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("could not read \"{}\"", path.display())),
+        }
+    }
+
+    /// Parses the contents of an answers file.
+    pub fn parse(contents: &str, file_path: &str) -> Result<Self, ConfigError> {
+        let mut answers = HashMap::new();
+
+        for (origin, key, value) in config::parse_values(contents, file_path)? {
+            let part = key
+                .split_once('.')
+                .and_then(|(name, key)| Some((name, key.strip_prefix("part_")?.parse().ok()?)));
+            let Some((name, part)) = part else {
+                return Err(ConfigError {
+                    origin,
+                    key: Some(key),
+                    message: "expected `part_1` or `part_2` in the table of an input".into(),
+                });
+            };
+            answers.insert((name.to_string(), part), value);
+        }
+
+        Ok(Self { answers })
+    }
+
+    /// Returns the recorded answer of `part` for the input `name`.
+    pub fn get(&self, name: &str, part: Part) -> Option<&str> {
+        self.answers
+            .get(&(name.to_string(), part))
+            .map(String::as_str)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use crate::part;
+
+    #[test]
+    fn parses_answers() {
+        let answers = Answers::parse(
+            "[alice]\npart_1 = 11\npart_2 = \"AB,CD\"\n\n[bob-2]\npart_1 = 7 # checked\n",
+            "answers.toml",
+        )
+        .unwrap();
+
+        assert_eq!(answers.get("alice", part!(1)), Some("11"));
+        assert_eq!(answers.get("alice", part!(2)), Some("AB,CD"));
+        assert_eq!(answers.get("bob-2", part!(1)), Some("7"));
+        assert_eq!(answers.get("bob-2", part!(2)), None);
+        assert_eq!(answers.get("carol", part!(1)), None);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let e = Answers::parse("part_1 = 1", "answers.toml").unwrap_err();
+        assert_eq!(e.key.as_deref(), Some("part_1"));
+
+        for contents in ["[alice]\nanswer = 1", "[alice]\npart_3 = 1"] {
+            let e = Answers::parse(contents, "answers.toml").unwrap_err();
+            assert_eq!(e.origin, "answers.toml:2");
+        }
+    }
+}
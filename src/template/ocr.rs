@@ -0,0 +1,271 @@
+//! Reads capital letters drawn with `#` in the fonts Advent of Code uses for letter-art answers.
+//!
+//! ```
+//! # use advent_of_code::template::ocr;
+//! let art = "\
+//! .##..#...
+//! #..#.#...
+//! #....#...
+//! #....#...
+//! #..#.#...
+//! .##..####";
+//! let letters = ocr::parse(art).unwrap();
+//! assert_eq!(letters.to_string(), "CL");
+//! ```
+use std::fmt::Display;
+
+/// Glyphs of the 4×6 font. Letters are separated by at least one blank column.
+#[rustfmt::skip]
+const SMALL_FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Glyphs of the 6×10 font.
+#[rustfmt::skip]
+const LARGE_FONT: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Text read from letter art.
+///
+/// # Display
+/// Displays as the recognized text, so it can be returned from a solution and submitted.
+/// The alternate form (`{:#}`) shows the art above the text, e.g. with `cargo solve <day> --verbose`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Letters {
+    text: String,
+    art: String,
+}
+
+impl Letters {
+    /// The recognized text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The art the text was read from, drawn with `#` and `.`.
+    pub fn art(&self) -> &str {
+        &self.art
+    }
+
+    /// Converts into the recognized text.
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+impl Display for Letters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}\n{}", self.art, self.text)
+        } else {
+            f.write_str(&self.text)
+        }
+    }
+}
+
+/// An error which can be returned when reading letter art.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// The art contains no lit cells.
+    Empty,
+    /// The art is neither 6 nor 10 rows high.
+    UnsupportedHeight(usize),
+    /// A letter at `column` is not part of the font.
+    UnknownGlyph { column: usize, glyph: String },
+}
+
+impl std::error::Error for OcrError {}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => f.write_str("the letter art is empty"),
+            OcrError::UnsupportedHeight(height) => write!(
+                f,
+                "letter art must be 6 or 10 rows high, found {height} rows"
+            ),
+            OcrError::UnknownGlyph { column, glyph } => {
+                write!(f, "unknown letter at column {column}:\n{glyph}")
+            }
+        }
+    }
+}
+
+/// Reads letter art from a multi-line string. `#` marks lit cells, any other character is blank.
+pub fn parse(art: &str) -> Result<Letters, OcrError> {
+    from_cells(art.lines().map(|line| line.chars().map(|c| c == '#')))
+}
+
+/// Reads letter art from rows of cells, `true` being lit.
+pub fn from_cells<R, C>(rows: R) -> Result<Letters, OcrError>
+where
+    R: IntoIterator<Item = C>,
+    C: IntoIterator<Item = bool>,
+{
+    let mut rows: Vec<Vec<bool>> = rows
+        .into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect();
+
+    // NOTE: blank rows around the letters, e.g. from a larger screen, are ignored.
+    while rows.last().is_some_and(|row| !row.contains(&true)) {
+        rows.pop();
+    }
+    let top = rows.iter().take_while(|row| !row.contains(&true)).count();
+    rows.drain(..top);
+
+    if rows.is_empty() {
+        return Err(OcrError::Empty);
+    }
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(width, false);
+    }
+
+    let text = match rows.len() {
+        6 => read_letters(&rows, SMALL_FONT)?,
+        10 => read_letters(&rows, LARGE_FONT)?,
+        height => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    Ok(Letters {
+        text,
+        art: draw(&rows),
+    })
+}
+
+fn read_letters<const H: usize>(
+    rows: &[Vec<bool>],
+    font: &[(char, [&str; H])],
+) -> Result<String, OcrError> {
+    let width = rows[0].len();
+    let is_blank = |x: usize| rows.iter().all(|row| !row[x]);
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if is_blank(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !is_blank(x) {
+            x += 1;
+        }
+
+        let glyph: Vec<String> = rows.iter().map(|row| draw_row(&row[start..x])).collect();
+        let letter = font
+            .iter()
+            .find(|(_, shape)| shape.iter().eq(glyph.iter()))
+            .map(|(letter, _)| *letter);
+
+        match letter {
+            Some(letter) => text.push(letter),
+            None => {
+                return Err(OcrError::UnknownGlyph {
+                    column: start,
+                    glyph: glyph.join("\n"),
+                })
+            }
+        }
+    }
+
+    Ok(text)
+}
+
+fn draw_row(row: &[bool]) -> String {
+    row.iter().map(|&lit| if lit { '#' } else { '.' }).collect()
+}
+
+fn draw(rows: &[Vec<bool>]) -> String {
+    rows.iter()
+        .map(|row| draw_row(row))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_cells, parse, OcrError, LARGE_FONT, SMALL_FONT};
+
+    fn render<const H: usize>(font: &[(char, [&str; H])], text: &str) -> String {
+        (0..H)
+            .map(|y| {
+                text.chars()
+                    .map(|c| font.iter().find(|(l, _)| *l == c).unwrap().1[y])
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_every_glyph() {
+        let small: String = SMALL_FONT.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(parse(&render(SMALL_FONT, &small)).unwrap().text(), small);
+
+        let large: String = LARGE_FONT.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(parse(&render(LARGE_FONT, &large)).unwrap().text(), large);
+    }
+
+    #[test]
+    fn reads_cells_with_margins() {
+        let art = format!("\n{}\n\n", render(SMALL_FONT, "EFGH").replace('.', " "));
+        let cells = art
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect::<Vec<_>>());
+
+        let letters = from_cells(cells).unwrap();
+        assert_eq!(letters.to_string(), "EFGH");
+        assert_eq!(
+            format!("{letters:#}"),
+            format!("{}\nEFGH", render(SMALL_FONT, "EFGH"))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_art() {
+        assert_eq!(parse("...\n..."), Err(OcrError::Empty));
+        assert_eq!(parse("#\n#"), Err(OcrError::UnsupportedHeight(2)));
+        assert!(matches!(
+            parse(&render(SMALL_FONT, "A").replace(".##.\n", "#..#\n")),
+            Err(OcrError::UnknownGlyph { column: 0, .. })
+        ));
+    }
+}
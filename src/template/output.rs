@@ -0,0 +1,166 @@
+//! Decides how the template writes to the terminal: whether to use ANSI styles and whether
+//! progress may overwrite the current line. Both are off when output is piped to a file or CI log.
+use std::{fmt::Display, io::IsTerminal, str::FromStr, sync::OnceLock};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// When to use ANSI styles, set with `--color <when>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Style output if stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = ColorChoiceFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(ColorChoiceFromStrError),
+        }
+    }
+}
+
+impl Display for ColorChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`ColorChoice`].
+#[derive(Debug)]
+pub struct ColorChoiceFromStrError;
+
+impl std::error::Error for ColorChoiceFromStrError {}
+
+impl Display for ColorChoiceFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `auto`, `always` or `never`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+struct Settings {
+    color: bool,
+    interactive: bool,
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Sets up output for this process. `interactive` overrides terminal detection, which
+/// solutions need when the template captures their output but shows it on a terminal.
+/// Later calls have no effect; without a call, output is set up as if `--color auto` was passed.
+pub fn init(color: ColorChoice, interactive: Option<bool>) {
+    let _ = SETTINGS.set(detect(color, interactive));
+}
+
+fn settings() -> &'static Settings {
+    SETTINGS.get_or_init(|| detect(ColorChoice::Auto, None))
+}
+
+fn detect(color: ColorChoice, interactive: Option<bool>) -> Settings {
+    let is_terminal = std::io::stdout().is_terminal();
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+    Settings {
+        color: resolve_color(color, no_color, is_terminal),
+        interactive: interactive.unwrap_or(is_terminal),
+    }
+}
+
+fn resolve_color(choice: ColorChoice, no_color: bool, is_terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => is_terminal && !no_color,
+    }
+}
+
+/// Whether output uses ANSI styles.
+pub fn color() -> bool {
+    settings().color
+}
+
+/// Whether progress may be drawn by overwriting the current line.
+/// Otherwise, progress is written as separate lines.
+pub fn interactive() -> bool {
+    settings().interactive
+}
+
+/// [`ANSI_BOLD`] if output is styled, nothing otherwise.
+pub fn bold() -> &'static str {
+    if color() {
+        ANSI_BOLD
+    } else {
+        ""
+    }
+}
+
+/// [`ANSI_ITALIC`] if output is styled, nothing otherwise.
+pub fn italic() -> &'static str {
+    if color() {
+        ANSI_ITALIC
+    } else {
+        ""
+    }
+}
+
+/// [`ANSI_RESET`] if output is styled, nothing otherwise.
+pub fn reset() -> &'static str {
+    if color() {
+        ANSI_RESET
+    } else {
+        ""
+    }
+}
+
+/// Returns the arguments that make a solution binary, whose output is captured and echoed,
+/// format it the same way as this process.
+pub fn forwarded_args() -> Vec<String> {
+    let color = if color() {
+        ColorChoice::Always
+    } else {
+        ColorChoice::Never
+    };
+
+    let mut args = vec!["--color".to_string(), color.to_string()];
+    if interactive() {
+        args.push("--interactive".into());
+    }
+    args
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{resolve_color, ColorChoice};
+
+    #[test]
+    fn resolves_color() {
+        assert!(resolve_color(ColorChoice::Auto, false, true));
+        assert!(!resolve_color(ColorChoice::Auto, true, true));
+        assert!(!resolve_color(ColorChoice::Auto, false, false));
+        assert!(resolve_color(ColorChoice::Always, true, false));
+        assert!(!resolve_color(ColorChoice::Never, false, true));
+    }
+
+    #[test]
+    fn parses_color_choice() {
+        for choice in [ColorChoice::Auto, ColorChoice::Always, ColorChoice::Never] {
+            assert_eq!(choice.to_string().parse::<ColorChoice>().unwrap(), choice);
+        }
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A valid part number of a puzzle (i.e. either 1 or 2).
+///
+/// # Display
+/// This value displays as a single digit.
+///
+/// ```
+/// # use advent_of_code::template::Part;
+/// let part = Part::new(2).unwrap();
+/// assert_eq!(part.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Part(u8);
+
+impl Part {
+    /// Creates a [`Part`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(part: u8) -> Option<Self> {
+        if part == 0 || part > 2 {
+            return None;
+        }
+        Some(Self(part))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(part: u8) -> Self {
+        Self(part)
+    }
+
+    /// Converts the [`Part`] into an [`u8`].
+    pub fn into_inner(self) -> u8 {
+        self.0
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<u8> for Part {
+    fn eq(&self, other: &u8) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part number of 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator that yields both parts of a puzzle.
+pub fn all_parts() -> AllParts {
+    AllParts::new()
+}
+
+/// An iterator that yields both parts of a puzzle.
+pub struct AllParts {
+    current: u8,
+}
+
+impl AllParts {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self { current: 1 }
+    }
+}
+
+impl Iterator for AllParts {
+    type Item = Part;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current > 2 {
+            return None;
+        }
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above 2.
+        let part = Part(self.current);
+        self.current += 1;
+
+        Some(part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Part`] value in a const context.
+#[macro_export]
+macro_rules! part {
+    ($part:expr) => {{
+        const _ASSERT: () = assert!(
+            $part == 1 || $part == 2,
+            concat!("invalid part number `", $part, "`, expecting 1 or 2"),
+        );
+        $crate::template::Part::__new_unchecked($part)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{all_parts, Part};
+
+    #[test]
+    fn all_parts_iterator() {
+        let mut iter = all_parts();
+
+        assert_eq!(iter.next(), Some(Part(1)));
+        assert_eq!(iter.next(), Some(Part(2)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part(1));
+        assert_eq!("2".parse::<Part>().unwrap(), Part(2));
+        assert!("0".parse::<Part>().is_err());
+        assert!("3".parse::<Part>().is_err());
+        assert!("one".parse::<Part>().is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,347 @@
+//! Offline library of the puzzle descriptions downloaded by aoc-cli.
+use std::{fs, io, ops::Range, path::Path};
+
+use crate::template::{config, markdown, Day, Error, ErrorContext, Part};
+
+/// Characters of context shown on each side of a match.
+const SNIPPET_CONTEXT: usize = 40;
+/// Maximum number of snippets shown per match.
+const MAX_SNIPPETS: usize = 2;
+/// Weight of a term occurring in the puzzle title, relative to one in the text.
+const TITLE_WEIGHT: usize = 10;
+/// Weight of the whole query occurring as a phrase, relative to a single term.
+const PHRASE_WEIGHT: usize = 5;
+
+/// A downloaded puzzle description, split into its parts.
+#[derive(Debug)]
+pub struct Puzzle {
+    pub day: Day,
+    pub title: String,
+    /// Markdown of each part that has been unlocked; the first part includes the title heading.
+    pub parts: Vec<String>,
+}
+
+impl Puzzle {
+    /// Splits the markdown written by aoc-cli into its parts.
+    pub fn parse(day: Day, markdown: &str) -> Self {
+        let mut parts = vec![String::new()];
+        let mut title = None;
+
+        for line in markdown.lines() {
+            // NOTE: headings are written as `\--- Part Two ---` or `## --- Part Two ---`.
+            let text = line.trim_start_matches(['#', '\\', '-', ' ']);
+            let is_heading = text.len() < line.len();
+            if is_heading && text.starts_with("Part Two") {
+                parts.push(String::new());
+            } else if is_heading && title.is_none() && text.starts_with("Day ") {
+                title = text
+                    .split_once(':')
+                    .map(|(_, title)| title.trim_end_matches(['-', ' ']).trim().to_string());
+            }
+
+            let part = parts.last_mut().unwrap();
+            part.push_str(line);
+            part.push('\n');
+        }
+
+        Self {
+            day,
+            title: title.unwrap_or_else(|| format!("Day {day}")),
+            parts,
+        }
+    }
+
+    /// Returns the markdown of `part`, if it has been unlocked.
+    pub fn part(&self, part: Part) -> Option<&str> {
+        self.parts
+            .get(usize::from(part.into_inner()) - 1)
+            .map(String::as_str)
+    }
+
+    /// Returns the markdown of all unlocked parts.
+    pub fn markdown(&self) -> String {
+        self.parts.concat()
+    }
+
+    /// Renders all unlocked parts, separated by a rule.
+    pub fn render(&self, options: &markdown::Options) -> String {
+        self.parts
+            .iter()
+            .map(|part| markdown::render(part, options))
+            .collect::<Vec<_>>()
+            .join(&markdown::separator(options))
+    }
+}
+
+/// Reads the puzzle description for `day`.
+pub fn load(day: Day) -> Result<Puzzle, Error> {
+    let path = Path::new(&config::get().paths.puzzles).join(format!("{day}.md"));
+    let markdown = fs::read_to_string(&path).with_context(|| {
+        format!(
+            "Could not read the puzzle for day {day} from \"{}\". Run `cargo download {day}` first.",
+            path.display()
+        )
+    })?;
+    Ok(Puzzle::parse(day, &markdown))
+}
+
+/// Reads all downloaded puzzle descriptions, ordered by day.
+pub fn load_all() -> Result<Vec<Puzzle>, Error> {
+    let dir = &config::get().paths.puzzles;
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e).with_context(|| format!("Could not read \"{dir}\"")),
+    };
+
+    let mut puzzles = vec![];
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("Could not read \"{dir}\""))?
+            .path();
+        if path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let Some(day) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<Day>().ok())
+        else {
+            continue;
+        };
+        let markdown = fs::read_to_string(&path)
+            .with_context(|| format!("Could not read \"{}\"", path.display()))?;
+        puzzles.push(Puzzle::parse(day, &markdown));
+    }
+
+    puzzles.sort_unstable_by_key(|puzzle| puzzle.day);
+    Ok(puzzles)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A part of a puzzle that matches a search query.
+#[derive(Debug)]
+pub struct SearchHit {
+    pub day: Day,
+    pub part: Part,
+    pub title: String,
+    pub score: usize,
+    pub snippets: Vec<Snippet>,
+}
+
+/// An excerpt of a puzzle text around a match.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snippet {
+    pub text: String,
+    /// Byte ranges of the matched terms in `text`.
+    pub matches: Vec<Range<usize>>,
+}
+
+/// Searches the parts of `puzzles` for the words of `query`.
+///
+/// A part matches if it contains every word, ignoring case. Matches are ranked by how often
+/// the words occur, with occurrences in the title and of the whole query weighing more.
+pub fn search(puzzles: &[Puzzle], query: &str) -> Vec<SearchHit> {
+    let terms = terms(query);
+    if terms.is_empty() {
+        return vec![];
+    }
+    let phrase = terms.join(" ");
+
+    let mut hits = vec![];
+    for puzzle in puzzles {
+        let title = puzzle.title.to_lowercase();
+
+        for (part, markdown) in crate::template::all_parts().zip(&puzzle.parts) {
+            let text = normalize_whitespace(&markdown::plain(markdown));
+            let lower = text.to_lowercase();
+
+            let counts: Vec<usize> = terms
+                .iter()
+                .map(|term| lower.matches(term.as_str()).count())
+                .collect();
+            if counts.contains(&0) {
+                continue;
+            }
+
+            let title_hits: usize = terms
+                .iter()
+                .map(|t| title.matches(t.as_str()).count())
+                .sum();
+            let phrase_hits = if terms.len() > 1 {
+                lower.matches(&phrase).count()
+            } else {
+                0
+            };
+            let score = counts.iter().sum::<usize>()
+                + TITLE_WEIGHT * title_hits
+                + PHRASE_WEIGHT * phrase_hits;
+
+            hits.push(SearchHit {
+                day: puzzle.day,
+                part,
+                title: puzzle.title.clone(),
+                score,
+                snippets: snippets(&text, &lower, &terms),
+            });
+        }
+    }
+
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.day.cmp(&b.day))
+            .then(a.part.cmp(&b.part))
+    });
+    hits
+}
+
+fn terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    terms.dedup();
+    terms
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Byte ranges in `lower` at which any of `terms` occurs, in order.
+fn match_ranges(lower: &str, terms: &[String]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = terms
+        .iter()
+        .flat_map(|term| {
+            lower
+                .match_indices(term.as_str())
+                .map(|(start, m)| start..start + m.len())
+        })
+        .collect();
+    ranges.sort_unstable_by_key(|range| range.start);
+    ranges
+}
+
+/// Cuts excerpts of `text` around the first matches, merging overlapping ones.
+fn snippets(text: &str, lower: &str, terms: &[String]) -> Vec<Snippet> {
+    // NOTE: lowercasing can change byte lengths for some scripts; fall back to the lowercase text.
+    let text = if text.len() == lower.len() {
+        text
+    } else {
+        lower
+    };
+    let ranges = match_ranges(lower, terms);
+
+    let mut windows: Vec<Range<usize>> = vec![];
+    for range in &ranges {
+        let start = floor_char_boundary(text, range.start.saturating_sub(SNIPPET_CONTEXT));
+        let end = ceil_char_boundary(text, range.end + SNIPPET_CONTEXT);
+        let full = windows.len() == MAX_SNIPPETS;
+        match windows.last_mut() {
+            Some(last) if start <= last.end => last.end = last.end.max(end),
+            _ if full => break,
+            _ => windows.push(start..end),
+        }
+    }
+
+    windows
+        .into_iter()
+        .map(|window| {
+            let prefix = if window.start > 0 { "…" } else { "" };
+            let suffix = if window.end < text.len() { "…" } else { "" };
+            let offset = prefix.len() as isize - window.start as isize;
+
+            Snippet {
+                text: format!("{prefix}{}{suffix}", &text[window.clone()]),
+                matches: ranges
+                    .iter()
+                    .filter(|range| range.start >= window.start && range.end <= window.end)
+                    .map(|range| {
+                        (range.start as isize + offset) as usize
+                            ..(range.end as isize + offset) as usize
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn floor_char_boundary(s: &str, mut i: usize) -> usize {
+    while !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+fn ceil_char_boundary(s: &str, mut i: usize) -> usize {
+    if i >= s.len() {
+        return s.len();
+    }
+    while !s.is_char_boundary(i) {
+        i += 1;
+    }
+    i
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{search, Puzzle};
+    use crate::template::Day;
+
+    const DAY_16: &str = "\\--- Day 16: Reindeer Maze ---\n----------\n\n\
+        It's time again for the *Reindeer Olympics*! Use Dijkstra or any other path finding.\n\n\
+        \\--- Part Two ---\n----------\n\n\
+        Now that you know what the best paths look like, find every best path through the maze.\n";
+
+    const DAY_21: &str = "\\--- Day 21: Keypad Conundrum ---\n----------\n\n\
+        The door is locked by a numeric keypad. A robot types on the keypad with a directional keypad.\n";
+
+    fn puzzles() -> Vec<Puzzle> {
+        vec![
+            Puzzle::parse(Day::new(16).unwrap(), DAY_16),
+            Puzzle::parse(Day::new(21).unwrap(), DAY_21),
+        ]
+    }
+
+    #[test]
+    fn splits_parts() {
+        let puzzle = Puzzle::parse(Day::new(16).unwrap(), DAY_16);
+        assert_eq!(puzzle.title, "Reindeer Maze");
+        assert_eq!(puzzle.parts.len(), 2);
+        assert!(puzzle.parts[1].starts_with("\\--- Part Two ---"));
+        assert_eq!(puzzle.markdown(), DAY_16);
+    }
+
+    #[test]
+    fn ranks_matches() {
+        let puzzles = puzzles();
+
+        let hits = search(&puzzles, "keypad");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].day, Day::new(21).unwrap());
+
+        let hits = search(&puzzles, "maze path");
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|hit| hit.day == Day::new(16).unwrap()));
+        assert!(hits[0].score >= hits[1].score);
+
+        assert!(search(&puzzles, "dijkstra keypad").is_empty());
+        assert!(search(&puzzles, "  ").is_empty());
+    }
+
+    #[test]
+    fn cuts_snippets() {
+        let puzzles = puzzles();
+        let hits = search(&puzzles, "DIJKSTRA");
+
+        let snippet = &hits[0].snippets[0];
+        assert!(snippet.text.starts_with('…'));
+        assert_eq!(&snippet.text[snippet.matches[0].clone()], "Dijkstra");
+    }
+}
@@ -1,62 +1,78 @@
-use std::{collections::HashSet, io};
+use std::collections::HashSet;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{output, Day, Error};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Outcome of running a set of days.
+pub struct MultiRun {
+    /// Timings of the days that ran successfully, if the run was timed.
+    pub timings: Option<Timings>,
+    /// Days whose solution failed to build or run.
+    pub failed: Vec<Day>,
+}
+
+impl MultiRun {
+    /// Returns an error if any of the days failed.
+    pub fn check(&self) -> Result<(), Error> {
+        if self.failed.is_empty() {
+            return Ok(());
+        }
+
+        let days: Vec<_> = self.failed.iter().map(Day::to_string).collect();
+        Err(Error::Solution(format!(
+            "the solutions for {} day(s) failed: {}",
+            days.len(),
+            days.join(", ")
+        )))
+    }
+}
+
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+) -> Result<MultiRun, Error> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failed: Vec<Day> = vec![];
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        println!("{}Day {day}{}", output::bold(), output::reset());
+        println!("------");
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        match child_commands::run_solution(day, is_timed, is_release) {
+            Ok(output) if output.is_empty() => println!("Not solved."),
+            Ok(output) => timings.push(child_commands::parse_exec_time(&output, day)),
+            Err(e @ (Error::Solution(_) | Error::Child { .. })) => {
+                eprintln!("{e}");
+                failed.push(day);
             }
-        });
+            Err(e) => return Err(e),
+        }
+    }
 
-    if is_timed {
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
+        let (bold, italic, reset) = (output::bold(), output::italic(), output::reset());
+        println!("\n{bold}Total (Run):{reset} {italic}{total_millis:.2}ms{reset}");
         Some(timings)
     } else {
         None
-    }
-}
-
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
-}
+    };
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+    Ok(MultiRun { timings, failed })
 }
 
 #[must_use]
@@ -67,10 +83,10 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::get_path_for_bin;
+    use crate::template::{config, output, Day, Error, ErrorContext, Part};
     use std::{
-        io::{BufRead, BufReader},
+        io::{self, BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
@@ -90,44 +106,137 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        args.push("--");
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        let forwarded = [config::forwarded_args(), output::forwarded_args()].concat();
+        args.extend(forwarded.iter().map(String::as_str));
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = spawn_cargo(&args)?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let broken_pipe = || io::Error::from(io::ErrorKind::BrokenPipe);
+        let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
 
         let mut output = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                eprintln!("{line}");
             });
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
+            let line = line.context("could not read solution output")?;
             println!("{line}");
             output.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let _ = thread.join();
+        let status = cmd.wait().context("could not wait for solution")?;
+
+        if !status.success() {
+            return Err(Error::from_solution_status(day, status));
+        }
 
         Ok(output)
     }
 
+    /// Benches the release build of the solution bin for `day` and returns its output without
+    /// printing it. `extra_args` are passed to the solution after the forwarded configuration.
+    pub fn bench_solution(day: Day, extra_args: &[String]) -> Result<Vec<String>, Error> {
+        let args = [&["--time".to_string()], extra_args].concat();
+        capture_solution(day, true, &args)
+    }
+
+    /// Runs the solution bin for `day` and returns its output without printing it.
+    /// `extra_args` are passed to the solution after the forwarded configuration.
+    pub fn capture_solution(
+        day: Day,
+        is_release: bool,
+        extra_args: &[String],
+    ) -> Result<Vec<String>, Error> {
+        let day_padded = day.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if is_release {
+            args.push("--release");
+        }
+
+        // NOTE: the output is parsed, not shown, so it is never styled or interactive.
+        let output = Command::new("cargo")
+            .args(args)
+            .args(["--", "--color", "never"])
+            .args(config::forwarded_args().iter().chain(extra_args))
+            .stdin(Stdio::null())
+            .output()
+            .map_err(Error::from_cargo_spawn)?;
+
+        if !output.status.success() {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            return Err(Error::from_solution_status(day, output.status));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    /// Extracts the result of each part from the output of a run, `None` if the part has no
+    /// result. Results spanning several lines are joined with newlines.
+    pub fn parse_answers(output: &[String]) -> Vec<(Part, Option<String>)> {
+        let mut answers: Vec<(Part, Option<String>)> = vec![];
+        let mut multiline = false;
+
+        for line in output {
+            let parsed = line.split_once(": ").and_then(|(label, rest)| {
+                Some((label.strip_prefix("Part ")?.parse::<Part>().ok()?, rest))
+            });
+            let Some((part, rest)) = parsed else {
+                if let (true, Some((_, Some(answer)))) = (multiline, answers.last_mut()) {
+                    if !answer.is_empty() {
+                        answer.push('\n');
+                    }
+                    answer.push_str(line);
+                }
+                continue;
+            };
+
+            // NOTE: strip the duration, e.g. `42 (1.2ms @ 100 samples)`.
+            let rest = rest.trim_end();
+            let result = match rest.rfind(" (") {
+                Some(idx) if rest.ends_with(')') => rest[..idx].trim_end(),
+                _ => rest,
+            };
+            multiline = result == "▼";
+            let answer = match result {
+                "✖" => None,
+                "▼" => Some(String::new()),
+                result => Some(result.to_string()),
+            };
+            answers.push((part, answer));
+        }
+
+        answers
+    }
+
+    /// Spawns `cargo` with piped output.
+    pub fn spawn_cargo(args: &[&str]) -> Result<std::process::Child, Error> {
+        Command::new("cargo")
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(Error::from_cargo_spawn)
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
@@ -136,6 +245,17 @@ pub mod child_commands {
             total_nanos: 0_f64,
         };
 
+        for (part, timing_str, nanos) in parse_part_times(output) {
+            timings.set_part(part, Some(timing_str.into()));
+            timings.total_nanos += nanos;
+        }
+
+        timings
+    }
+
+    /// Extracts the benched time of each part from the output of a timed run, as printed and in
+    /// nanoseconds.
+    pub fn parse_part_times(output: &[String]) -> Vec<(Part, &str, f64)> {
         output
             .iter()
             .filter_map(|l| {
@@ -148,20 +268,16 @@ pub mod child_commands {
                     return None;
                 };
 
-                let part = l.split(':').next()?;
+                let part = l
+                    .split(':')
+                    .next()?
+                    .trim()
+                    .strip_prefix("Part ")?
+                    .parse::<Part>()
+                    .ok()?;
                 Some((part, timing_str, nanos))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
-            });
-
-        timings
+            .collect()
     }
 
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
@@ -174,7 +290,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +321,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
         use crate::day;
 
@@ -253,5 +369,22 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(&[
+                "Part 1: 10 (1) (2.1ms)".into(),
+                "Part 2: ▼  (3.0µs)".into(),
+                "#..#".into(),
+                ".##.".into(),
+            ]);
+            assert_eq!(res.len(), 2);
+            assert_eq!(res[0].1.as_deref(), Some("10 (1)"));
+            assert_eq!(res[1].1.as_deref(), Some("#..#\n.##."));
+
+            let res = parse_answers(&["Part 2: ✖        ".into()]);
+            assert_eq!(res[0].0, 2);
+            assert_eq!(res[0].1, None);
+        }
     }
 }
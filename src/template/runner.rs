@@ -1,33 +1,213 @@
-/// Encapsulates code that interacts with solution functions.
+//! Encapsulates code that interacts with solution functions.
+use std::cmp;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::aoc_cli::SubmitOutcome;
+use crate::template::output::{self, ColorChoice};
+use crate::template::{aoc_cli, config, input, Day, Error, ErrorContext, Part};
+use crate::{trace, viz};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Arguments passed to a solution binary, either by the template or by hand after `--`.
+#[derive(Debug, Default)]
+pub struct RunnerArgs {
+    /// `--time`: bench each part after running it.
+    pub time: bool,
+    /// `--submit <part>`: submit the answer of this part via aoc-cli.
+    pub submit: Option<Part>,
+    /// `--config <path>`: configuration file forwarded by the template.
+    pub config: Option<String>,
+    /// `--set <key>=<value>`: configuration overrides forwarded by the template.
+    pub overrides: Vec<String>,
+    /// `--color <when>`: whether to style output.
+    pub color: ColorChoice,
+    /// `--interactive`: output is shown on a terminal, even though stdout is captured by the template.
+    pub interactive: bool,
+    /// `--verbose`: print results in their alternate form (`{:#}`), e.g. letter art next to its text.
+    pub verbose: bool,
+    /// `--input <path>`: read this file instead of the day's input, e.g. a named input.
+    pub input: Option<String>,
+    /// `--variant <name>`: run this variant of the solution instead of `part_one` and `part_two`.
+    pub variant: Option<String>,
+    /// `--variants`: print the names of the solution's variants and exit.
+    pub list_variants: bool,
+    /// `--trace`: write the solution's traces, see [`crate::trace`].
+    pub trace: bool,
+    /// `--viz <target>`: record the solution's frames and write them to `target`, see [`crate::viz`].
+    pub viz: Option<viz::Target>,
+    /// `--fps <n>`: frames per second of the visualization, instead of `viz.fps`.
+    pub fps: Option<u64>,
+}
+
+impl RunnerArgs {
+    /// Parses the arguments of the current process and loads the configuration they point to.
+    /// Exits the process if either is invalid.
+    pub fn from_env() -> Self {
+        let args = Self::parse(pico_args::Arguments::from_env()).unwrap_or_else(|e| {
+            Error::Usage(format!("{e}. Format: cargo solve 1 --submit 1")).exit()
+        });
+
+        output::init(args.color, args.interactive.then_some(true));
+        if let Err(e) = config::init(args.config.as_deref(), &args.overrides) {
+            Error::Config(e).exit();
+        }
+
+        args
+    }
+
+    fn parse(mut args: pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let parsed = Self {
+            time: args.contains("--time"),
+            submit: args.opt_value_from_str("--submit")?,
+            config: args.opt_value_from_str("--config")?,
+            overrides: args.values_from_str("--set")?,
+            color: args.opt_value_from_str("--color")?.unwrap_or_default(),
+            interactive: args.contains("--interactive"),
+            verbose: args.contains("--verbose"),
+            input: args.opt_value_from_str("--input")?,
+            variant: args.opt_value_from_str("--variant")?,
+            list_variants: args.contains("--variants"),
+            trace: args.contains("--trace"),
+            viz: args.opt_value_from_str("--viz")?,
+            fps: args.opt_value_from_str("--fps")?,
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Name of the variant made of `part_one` and `part_two`.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Returns the variant selected with `--variant`, [`DEFAULT_VARIANT`] if none is.
+/// Handles `--variants` and exits the process if the variant is unknown.
+pub fn select_variant<'a>(args: &'a RunnerArgs, variants: &[&str]) -> &'a str {
+    if args.list_variants {
+        println!("{DEFAULT_VARIANT}");
+        variants.iter().for_each(|variant| println!("{variant}"));
+        std::process::exit(0);
+    }
+
+    match args.variant.as_deref() {
+        None => DEFAULT_VARIANT,
+        Some(variant) if variant == DEFAULT_VARIANT || variants.contains(&variant) => variant,
+        Some(variant) => {
+            let known = [&[DEFAULT_VARIANT], variants].concat().join(", ");
+            Error::Usage(format!(
+                "unknown variant `{variant}`, this solution has: {known}"
+            ))
+            .exit()
+        }
+    }
+}
+
+/// Starts tracing `day` as configured in `[trace]` if `--trace` is set. Exits the process if the
+/// trace file can't be created.
+pub fn init_trace(day: Day, args: &RunnerArgs) {
+    if !args.trace {
+        return;
+    }
+    let options = &config::get().trace;
+    trace::init(day, options.level, options.file.as_deref())
+        .context("could not create the trace file")
+        .unwrap_or_else(|e| e.exit());
+}
+
+/// Starts recording frames as configured in `[viz]` if `--viz` is set.
+pub fn init_viz(args: &RunnerArgs) {
+    let Some(target) = &args.viz else {
+        return;
+    };
+    let options = &config::get().viz;
+    viz::init(
+        target.clone(),
+        viz::Options {
+            fps: args.fps.unwrap_or(options.fps),
+            scale: options.scale as usize,
+            max_frames: options.max_frames as usize,
+        },
+    );
+}
+
+/// Writes the frames recorded with `--viz`. Exits the process if they can't be written.
+pub fn finish_viz() {
+    viz::finish()
+        .context("could not write the visualization")
+        .unwrap_or_else(|e| e.exit());
+}
+
+/// Warns if `--submit` names a part that this solution does not run.
+pub fn check_submit(args: &RunnerArgs, parts: &[Part]) {
+    if let Some(part) = args.submit.filter(|part| !parts.contains(part)) {
+        eprintln!("Warning: part {part} is not run by this solution, nothing will be submitted.");
+    }
+}
+
+/// Reads the puzzle input for `day`, or the file at `path` if given. Exits the process if it
+/// can't be read.
+pub fn read_input(day: Day, path: Option<&str>) -> String {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => config::get()
+            .paths
+            .folder("inputs")
+            .join(format!("{day}.txt")),
+    };
+    input::read(&path)
+        .with_context(|| format!("could not open input file \"{}\"", path.display()))
+        .unwrap_or_else(|e| e.exit())
+}
+
+pub fn run_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: Part,
+    args: &RunnerArgs,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // NOTE: without a terminal, the intermediate result can't be overwritten and is skipped.
+    let (result, duration, samples) = run_timed(func, input, args.time, &part_str, |result| {
+        if output::interactive() {
+            print_result(result, &part_str, "", args.verbose);
+        }
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&duration, samples),
+        args.verbose,
+    );
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        if args.submit == Some(part) {
+            if let Err(e) = submit_result(result, day, part) {
+                e.exit();
+            }
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. `bench.budget_ms` of execution time or `bench.min_samples` samples, whatever take longer.)
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
+    label: &str,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
@@ -41,8 +221,8 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let run = if is_timed {
+        bench(func, input, &base_time, label)
     } else {
         (base_time, 1)
     };
@@ -50,22 +230,45 @@ fn run_timed<I: Copy, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Whether a bench is running the samples of a part, see [`benching`].
+static BENCHING: AtomicBool = AtomicBool::new(false);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Whether a bench is running the samples of a part right now. [`trace`] and [`viz`] drop their
+/// output meanwhile: the first run already wrote it, the samples would repeat it and skew the
+/// timings.
+#[inline]
+pub fn benching() -> bool {
+    BENCHING.load(Ordering::Relaxed)
+}
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    label: &str,
+) -> (Duration, u128) {
+    let bench = &config::get().bench;
+    let bench_iterations = (Duration::from_millis(bench.budget_ms).as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(u128::from(bench.min_samples), u128::from(bench.max_samples));
+
+    if output::interactive() {
+        print!(" > {}benching{}", output::italic(), output::reset());
+        let _ = stdout().flush();
+    } else {
+        // NOTE: stderr keeps progress out of the output that `cargo all` parses timings from.
+        eprintln!("{label}: benching {bench_iterations} samples...");
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
+    BENCHING.store(true, Ordering::Relaxed);
     for _ in 0..bench_iterations {
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
+    BENCHING.store(false, Ordering::Relaxed);
 
     (
         #[allow(clippy::cast_possible_truncation)]
@@ -90,26 +293,34 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str, verbose: bool) {
     let is_intermediate_result = duration_str.is_empty();
+    // NOTE: the final result overwrites the intermediate one, which is only printed to terminals.
+    let line_start = if output::interactive() { "\r" } else { "" };
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
+            let result = if verbose {
+                format!("{result:#}")
+            } else {
+                result.to_string()
+            };
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
+                    print!("{line_start}");
                     println!("{str}");
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let (bold, reset) = (output::bold(), output::reset());
+                let str = format!("{part}: {bold}{result}{reset}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
+                    print!("{line_start}");
                     println!("{str}");
                 }
             }
@@ -117,49 +328,49 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
         None => {
             if is_intermediate_result {
                 print!("{part}: ✖");
+            } else if output::interactive() {
+                println!("\r{part}: ✖             ");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: ✖");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
-
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
+/// Rate limits up to this duration are waited out and the answer is submitted again.
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+/// Try to submit one part of the solution if aoc-cli is installed.
+/// Fails unless the answer was accepted or the part was solved before.
+fn submit_result<T: Display>(result: T, day: Day, part: Part) -> Result<(), Error> {
+    aoc_cli::check()?;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    let answer = result.to_string();
+    let mut retried = false;
 
-    if part_submit != part {
-        return None;
-    }
+    loop {
+        println!("Submitting result via aoc-cli...");
+        let outcome = aoc_cli::submit(day, part, &answer)?;
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+        println!("---");
+        match outcome {
+            SubmitOutcome::Correct => {
+                let (bold, reset) = (output::bold(), output::reset());
+                println!("🎄 {bold}{outcome}{reset} Part {part} of day {day} is solved.");
+                return Ok(());
+            }
+            SubmitOutcome::AlreadySolved | SubmitOutcome::Unknown => {
+                println!("{outcome}");
+                return Ok(());
+            }
+            SubmitOutcome::RateLimited { wait: Some(wait) }
+                if !retried && wait <= MAX_RETRY_WAIT =>
+            {
+                println!("{outcome} Waiting to submit again...");
+                thread::sleep(wait + Duration::from_secs(1));
+                retried = true;
+            }
+            outcome => return Err(Error::Submission(outcome)),
+        }
     }
-
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
 }
@@ -0,0 +1,185 @@
+//! Tracing for solutions: diagnostics that stay out of the printed results and the timings.
+//!
+//! ```ignore
+//! use advent_of_code::trace;
+//!
+//! trace!("{} robots", robots.len()); // at `debug`
+//! trace!(info, "the tree shows after {seconds} seconds");
+//! ```
+//!
+//! Traces are only written when a day runs with `cargo solve <day> --trace`, up to the level set
+//! as `trace.level`. They go to stderr, or to the file set as `trace.file`, and are dropped during
+//! the iterations of a bench. While tracing is off, a trace costs a single comparison and its
+//! arguments are not evaluated.
+use std::{
+    fmt::{self, Display},
+    fs::{self, File},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+use crate::template::{runner, Day};
+
+/// How detailed a trace is, from the least to the most detailed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Level::ALL
+            .into_iter()
+            .find(|level| level.as_str() == s)
+            .ok_or_else(|| {
+                format!("unknown level `{s}`, expecting one of error, warn, info, debug or trace")
+            })
+    }
+}
+
+/// The most detailed level written, `0` while tracing is off.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static SINK: OnceLock<Sink> = OnceLock::new();
+
+/// Where the traces of a day go.
+struct Sink {
+    day: Day,
+    /// `None` for stderr.
+    file: Option<Mutex<File>>,
+}
+
+/// Starts writing the traces of `day` up to `level`, to stderr or to `file`. A `{day}` in the
+/// path of the file is replaced with the day, e.g. `data/traces/{day}.log`.
+pub fn init(day: Day, level: Level, file: Option<&str>) -> io::Result<()> {
+    let file = match file {
+        Some(path) => {
+            let path = PathBuf::from(path.replace("{day}", &day.to_string()));
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+            Some(Mutex::new(File::create(path)?))
+        }
+        None => None,
+    };
+
+    if SINK.set(Sink { day, file }).is_ok() {
+        MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Whether a trace at `level` is written right now.
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed) && !runner::benching()
+}
+
+/// Writes a trace, prefixed with its day and level, e.g. `[14 debug] 500 robots`.
+/// Use [`trace!`](crate::trace!) instead.
+#[doc(hidden)]
+pub fn write(level: Level, args: fmt::Arguments) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    // NOTE: multi-line traces, e.g. grids, start on their own line to keep their columns aligned.
+    let message = args.to_string();
+    let separator = if message.contains('\n') { "\n" } else { " " };
+    let line = format!("[{} {level}]{separator}{message}\n", sink.day);
+
+    // NOTE: a failed write must not fail the solution, the trace is lost.
+    match &sink.file {
+        Some(file) => {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.write_all(line.as_bytes());
+            }
+        }
+        None => {
+            let _ = io::stderr().write_all(line.as_bytes());
+        }
+    }
+}
+
+/// Writes a trace if tracing is on, see [`crate::trace`]. The level (`error`, `warn`, `info`,
+/// `debug` or `trace`) may precede the format string and defaults to `debug`.
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $fmt:literal $($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace!(@level $level)) {
+            $crate::trace::write($crate::trace!(@level $level), format_args!($fmt $($arg)*));
+        }
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::trace!(debug, $fmt $($arg)*)
+    };
+
+    (@level error) => { $crate::trace::Level::Error };
+    (@level warn) => { $crate::trace::Level::Warn };
+    (@level info) => { $crate::trace::Level::Info };
+    (@level debug) => { $crate::trace::Level::Debug };
+    (@level trace) => { $crate::trace::Level::Trace };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{enabled, Level};
+
+    #[test]
+    fn parses_levels() {
+        for level in Level::ALL {
+            assert_eq!(level.to_string().parse(), Ok(level));
+        }
+        assert!("verbose".parse::<Level>().is_err());
+        assert!(Level::Error < Level::Trace);
+    }
+
+    #[test]
+    fn is_off_until_initialized() {
+        // NOTE: the library's tests never start tracing, so nothing is written.
+        assert!(!enabled(Level::Error));
+        let mut evaluated = false;
+        crate::trace!(error, "{}", {
+            evaluated = true;
+            1
+        });
+        assert!(!evaluated);
+    }
+}
@@ -0,0 +1,259 @@
+//! A minimal encoder of animated GIFs (GIF89a) with up to 256 colors.
+use std::collections::HashMap;
+
+use super::Rgb;
+use crate::grid::{Grid, Point};
+
+/// Codes of the LZW compression are at most 12 bits long.
+const MAX_CODE: u16 = 1 << 12;
+
+/// Encodes `frames` as a looping animation played at `fps` frames per second. Frames smaller
+/// than the largest one are padded with black.
+pub(super) fn encode(frames: &[Grid<Rgb>], fps: u64) -> Result<Vec<u8>, String> {
+    let width = frames.iter().map(Grid::width).max().unwrap_or_default();
+    let height = frames.iter().map(Grid::height).max().unwrap_or_default();
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(format!(
+            "the frames are {width}x{height} pixels, a GIF can't be larger than 65535x65535"
+        ));
+    };
+
+    let mut palette: HashMap<Rgb, u8> = HashMap::new();
+    let mut colors: Vec<Rgb> = vec![];
+    let mut images: Vec<Vec<u8>> = vec![];
+    for frame in frames {
+        let mut indices = Vec::with_capacity(usize::from(width) * usize::from(height));
+        for y in 0..i32::from(height) {
+            for x in 0..i32::from(width) {
+                let color = frame.get(Point::new(x, y)).copied().unwrap_or_default();
+                let index = match palette.get(&color) {
+                    Some(index) => *index,
+                    None => {
+                        let index = u8::try_from(colors.len())
+                            .map_err(|_| "the frames use more than 256 colors".to_string())?;
+                        palette.insert(color, index);
+                        colors.push(color);
+                        index
+                    }
+                };
+                indices.push(index);
+            }
+        }
+        images.push(indices);
+    }
+
+    // NOTE: the color table holds a power of two colors, at least two.
+    let table_bits = (1..=8u8)
+        .find(|bits| 1 << bits >= colors.len())
+        .unwrap_or(8);
+    let min_code_size = table_bits.max(2);
+    let delay = u16::try_from((100 + fps / 2) / fps.max(1))
+        .unwrap_or(u16::MAX)
+        .max(1);
+
+    let mut out = b"GIF89a".to_vec();
+    out.extend(width.to_le_bytes());
+    out.extend(height.to_le_bytes());
+    // NOTE: a global color table of 8-bit colors, followed by the background and aspect ratio.
+    out.extend([0xf0 | (table_bits - 1), 0, 0]);
+    for i in 0..1 << table_bits {
+        let Rgb(r, g, b) = colors.get(i).copied().unwrap_or_default();
+        out.extend([r, g, b]);
+    }
+    // NOTE: the application extension that makes the animation loop forever.
+    out.extend([0x21, 0xff, 0x0b]);
+    out.extend(b"NETSCAPE2.0");
+    out.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    for indices in &images {
+        out.extend([0x21, 0xf9, 0x04, 0x04]);
+        out.extend(delay.to_le_bytes());
+        out.extend([0x00, 0x00]);
+
+        out.extend([0x2c, 0x00, 0x00, 0x00, 0x00]);
+        out.extend(width.to_le_bytes());
+        out.extend(height.to_le_bytes());
+        out.push(0x00);
+
+        out.push(min_code_size);
+        for block in compress(indices, min_code_size).chunks(255) {
+            out.push(block.len() as u8);
+            out.extend(block);
+        }
+        out.push(0x00);
+    }
+
+    out.push(0x3b);
+    Ok(out)
+}
+
+/// Compresses color indices with the variable-length LZW of GIFs.
+fn compress(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut out = BitWriter::default();
+    let mut size = min_code_size + 1;
+    let mut next = end + 1;
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+
+    out.write(clear, size);
+    let Some((&first, rest)) = indices.split_first() else {
+        out.write(end, size);
+        return out.finish();
+    };
+
+    let mut prefix = u16::from(first);
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+
+        out.write(prefix, size);
+        // NOTE: decoders widen their codes one code late, after reading the one that fills the
+        // table up to the current width.
+        if next >= 1 << size && size < 12 {
+            size += 1;
+        }
+        if next < MAX_CODE {
+            table.insert((prefix, index), next);
+            next += 1;
+        } else {
+            out.write(clear, size);
+            table.clear();
+            next = end + 1;
+            size = min_code_size + 1;
+        }
+        prefix = u16::from(index);
+    }
+
+    out.write(prefix, size);
+    if next >= 1 << size && size < 12 {
+        size += 1;
+    }
+    out.write(end, size);
+    out.finish()
+}
+
+/// Packs codes into bytes, least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compress, encode};
+    use crate::check::Rng;
+    use crate::grid::Grid;
+    use crate::viz::Rgb;
+
+    /// Decompresses LZW codes the way GIF viewers do.
+    fn decompress(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let reset = || -> Vec<Vec<u8>> { (0..clear + 2).map(|i| vec![i as u8]).collect() };
+
+        let (mut table, mut size, mut previous) = (reset(), min_code_size + 1, None::<Vec<u8>>);
+        let (mut buffer, mut bits, mut bytes) = (0u32, 0u8, bytes.iter());
+        let mut out = vec![];
+
+        loop {
+            while bits < size {
+                buffer |= u32::from(*bytes.next().expect("the codes should end")) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << size) - 1)) as usize;
+            buffer >>= size;
+            bits -= size;
+
+            if code == clear {
+                (table, size, previous) = (reset(), min_code_size + 1, None);
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [&previous[..], &previous[..1]].concat(),
+                (None, None) => panic!("unknown code {code}"),
+            };
+            out.extend(&entry);
+            if let Some(previous) = previous {
+                if table.len() < 1 << 12 {
+                    table.push([&previous[..], &entry[..1]].concat());
+                }
+            }
+            if table.len() == 1 << size && size < 12 {
+                size += 1;
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn compresses_losslessly() {
+        let mut rng = Rng::new(7);
+        let runs: Vec<u8> = (0..5000).map(|i| (i / 700) as u8).collect();
+        let noise: Vec<u8> = (0..20_000).map(|_| rng.between(0, 3) as u8).collect();
+        let wide: Vec<u8> = (0..20_000).map(|_| rng.between(0, 255) as u8).collect();
+
+        for (indices, min_code_size) in [
+            (vec![], 2),
+            (vec![1], 2),
+            (vec![0, 0, 0, 0, 0, 0, 0], 2),
+            (runs, 4),
+            (noise, 2),
+            (wide, 8),
+        ] {
+            let compressed = compress(&indices, min_code_size);
+            assert_eq!(decompress(&compressed, min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn encodes_animations() {
+        let frames = [
+            Grid::new(3, 2, Rgb::WHITE),
+            Grid::from_fn(2, 2, |p| Rgb(p.x as u8 * 100, 0, 0)),
+        ];
+        let gif = encode(&frames, 10).unwrap();
+
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(&gif[6..10], [3, 0, 2, 0]);
+        // NOTE: white, black (padding) and one shade of red fit a table of four colors.
+        assert_eq!(gif[10], 0xf1);
+        assert!(gif.iter().filter(|b| **b == 0x2c).count() >= 2);
+        assert_eq!(gif.last(), Some(&0x3b));
+
+        let colorful: Vec<Grid<Rgb>> =
+            vec![Grid::from_fn(17, 17, |p| Rgb(p.x as u8, p.y as u8, 0))];
+        assert!(encode(&colorful, 10).is_err());
+    }
+}
@@ -0,0 +1,402 @@
+//! Visualizations of solutions: a solution pushes frames, e.g. its grid after every step, and
+//! `cargo solve <day> --viz <target>` turns them into an animation.
+//!
+//! ```ignore
+//! use advent_of_code::viz;
+//!
+//! for step in 0..steps {
+//!     // ...
+//!     viz::push_chars(&grid);
+//! }
+//! ```
+//!
+//! The target is an animated GIF (`out.gif`), a sequence of PPM images (`out/`), a PNG image of
+//! the final frame (`out.png`) or a playback in the terminal (`term`). Frames are kept in memory
+//! until the solution finishes. Beyond `viz.max_frames`, every other frame is dropped, so long
+//! simulations are sped up rather than cut short. Like traces, frames are not recorded during
+//! the iterations of a bench, and pushing one costs a single comparison unless `--viz` is set.
+use std::{
+    fmt::{self, Display},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use crate::grid::{Grid, Point};
+use crate::template::{runner, Error, ErrorContext};
+
+mod gif;
+mod png;
+mod ppm;
+mod term;
+
+/// A color of a frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// Perceived brightness, from 0 to 255.
+    pub fn luminance(self) -> u8 {
+        ((299 * u32::from(self.0) + 587 * u32::from(self.1) + 114 * u32::from(self.2)) / 1000) as u8
+    }
+}
+
+/// Colors of the characters of a frame when it is drawn as an image.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette(&'static [(char, Rgb)]);
+
+impl Palette {
+    /// Colors of the characters puzzles commonly use for walls, floors, boxes and robots.
+    pub const DEFAULT: Palette = Palette(&[
+        ('#', Rgb(128, 128, 128)),
+        ('.', Rgb(16, 16, 16)),
+        (' ', Rgb(16, 16, 16)),
+        ('@', Rgb(230, 60, 60)),
+        ('^', Rgb(230, 60, 60)),
+        ('O', Rgb(230, 180, 60)),
+        ('[', Rgb(230, 180, 60)),
+        (']', Rgb(230, 180, 60)),
+        ('X', Rgb(60, 120, 230)),
+        ('S', Rgb(60, 200, 90)),
+        ('E', Rgb(60, 200, 90)),
+        ('█', Rgb(60, 200, 90)),
+    ]);
+
+    pub const fn new(colors: &'static [(char, Rgb)]) -> Self {
+        Self(colors)
+    }
+
+    /// Returns the color of `c`: its own, the default one or one derived from the character.
+    pub fn color(&self, c: char) -> Rgb {
+        let find = |palette: &Palette| {
+            palette
+                .0
+                .iter()
+                .find(|(other, _)| *other == c)
+                .map(|(_, color)| *color)
+        };
+        find(self)
+            .or_else(|| find(&Self::DEFAULT))
+            .unwrap_or_else(|| {
+                let c = c as u32;
+                Rgb(
+                    (55 + c * 53 % 200) as u8,
+                    (55 + c * 97 % 200) as u8,
+                    (55 + c * 29 % 200) as u8,
+                )
+            })
+    }
+}
+
+/// A picture of a solution's state.
+#[derive(Debug, Clone)]
+pub enum Frame {
+    /// A grid of characters, drawn in the colors of its palette.
+    Chars(Grid<char>, Palette),
+    /// A grid of colors.
+    Colors(Grid<Rgb>),
+}
+
+impl Frame {
+    /// The colors of the frame, with each cell drawn as a `scale`×`scale` square.
+    pub fn to_colors(&self, scale: usize) -> Grid<Rgb> {
+        let (width, height) = match self {
+            Frame::Chars(grid, _) => (grid.width(), grid.height()),
+            Frame::Colors(grid) => (grid.width(), grid.height()),
+        };
+        let scale = scale.max(1);
+
+        Grid::from_fn(width * scale, height * scale, |point| {
+            let cell = Point::new(point.x / scale as i32, point.y / scale as i32);
+            match self {
+                Frame::Chars(grid, palette) => palette.color(grid[cell]),
+                Frame::Colors(grid) => grid[cell],
+            }
+        })
+    }
+}
+
+/// Where the frames of a solution go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// An animated GIF, e.g. `out.gif`.
+    Gif(PathBuf),
+    /// A directory receiving one PPM image per frame, e.g. `out/`.
+    Ppm(PathBuf),
+    /// A PNG image of the last frame, e.g. `out.png`.
+    Png(PathBuf),
+    /// A playback in the terminal, `term`.
+    Terminal,
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "term" {
+            Ok(Target::Terminal)
+        } else if s.ends_with(".gif") {
+            Ok(Target::Gif(s.into()))
+        } else if s.ends_with(".png") {
+            Ok(Target::Png(s.into()))
+        } else if s.ends_with('/') || Path::new(s).is_dir() {
+            Ok(Target::Ppm(s.into()))
+        } else {
+            Err(format!(
+                "expected a `.gif` or `.png` file, a directory ending in `/` or `term`, found `{s}`"
+            ))
+        }
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Gif(path) | Target::Png(path) => write!(f, "{}", path.display()),
+            Target::Ppm(path) => write!(f, "{}/", path.display().to_string().trim_end_matches('/')),
+            Target::Terminal => f.write_str("term"),
+        }
+    }
+}
+
+/// How frames are recorded and played, see `[viz]` in `aoc.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Frames per second of the animation.
+    pub fps: u64,
+    /// Size of a cell in the pixels of an image.
+    pub scale: usize,
+    /// The most frames kept in memory.
+    pub max_frames: usize,
+}
+
+/// The frames recorded so far.
+struct Recorder {
+    target: Target,
+    options: Options,
+    frames: Vec<Frame>,
+    /// Frames pushed so far, including dropped ones.
+    pushed: usize,
+    /// Every `stride`-th pushed frame is kept.
+    stride: usize,
+    /// The latest frame if it was dropped, kept so the animation ends on the final state.
+    last: Option<Frame>,
+}
+
+impl Recorder {
+    fn push(&mut self, frame: Frame) {
+        let keep = self.pushed.is_multiple_of(self.stride);
+        self.pushed += 1;
+        if !keep {
+            self.last = Some(frame);
+            return;
+        }
+
+        self.frames.push(frame);
+        self.last = None;
+        if self.frames.len() > self.options.max_frames {
+            self.frames = std::mem::take(&mut self.frames)
+                .into_iter()
+                .step_by(2)
+                .collect();
+            self.stride *= 2;
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Starts recording the frames pushed by the solution, to be written to `target` by [`finish`].
+pub fn init(target: Target, options: Options) {
+    let recorder = Recorder {
+        target,
+        options,
+        frames: vec![],
+        pushed: 0,
+        stride: 1,
+        last: None,
+    };
+    if let Ok(mut slot) = RECORDER.lock() {
+        *slot = Some(recorder);
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Whether pushed frames are recorded right now. Check it to skip building expensive frames.
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed) && !runner::benching()
+}
+
+/// Records a frame if `--viz` is set.
+pub fn push(frame: Frame) {
+    if !enabled() {
+        return;
+    }
+    if let Ok(mut slot) = RECORDER.lock() {
+        if let Some(recorder) = slot.as_mut() {
+            recorder.push(frame);
+        }
+    }
+}
+
+/// Records a grid of characters in the [`Palette::DEFAULT`] colors.
+pub fn push_chars(grid: &Grid<char>) {
+    push_chars_with(grid, Palette::DEFAULT);
+}
+
+/// Records a grid of characters in the colors of `palette`.
+pub fn push_chars_with(grid: &Grid<char>, palette: Palette) {
+    if enabled() {
+        push(Frame::Chars(grid.clone(), palette));
+    }
+}
+
+/// Records a grid of colors.
+pub fn push_colors(grid: &Grid<Rgb>) {
+    if enabled() {
+        push(Frame::Colors(grid.clone()));
+    }
+}
+
+/// Writes the recorded frames to their target and stops recording.
+pub fn finish() -> Result<(), Error> {
+    ENABLED.store(false, Ordering::Relaxed);
+    let Some(recorder) = RECORDER.lock().ok().and_then(|mut slot| slot.take()) else {
+        return Ok(());
+    };
+
+    let Recorder {
+        target,
+        options,
+        mut frames,
+        pushed,
+        last,
+        ..
+    } = recorder;
+    frames.extend(last);
+
+    if frames.is_empty() {
+        eprintln!("Warning: the solution pushed no frames, there is nothing to visualize.");
+        return Ok(());
+    }
+    if frames.len() < pushed {
+        eprintln!(
+            "Note: kept {} of {pushed} frames, see `viz.max_frames`.",
+            frames.len()
+        );
+    }
+
+    match &target {
+        Target::Gif(path) => {
+            let images: Vec<Grid<Rgb>> = frames
+                .iter()
+                .map(|frame| frame.to_colors(options.scale))
+                .collect();
+            let bytes = gif::encode(&images, options.fps).map_err(Error::Solution)?;
+            fs::write(path, bytes)
+                .with_context(|| format!("could not write \"{}\"", path.display()))?;
+        }
+        Target::Ppm(folder) => {
+            fs::create_dir_all(folder)
+                .with_context(|| format!("could not create \"{}\"", folder.display()))?;
+            let digits = frames.len().to_string().len().max(4);
+            for (i, frame) in frames.iter().enumerate() {
+                let path = folder.join(format!("{:0digits$}.ppm", i + 1));
+                fs::write(&path, ppm::encode(&frame.to_colors(options.scale)))
+                    .with_context(|| format!("could not write \"{}\"", path.display()))?;
+            }
+        }
+        Target::Png(path) => {
+            // NOTE: a still image shows where the solution ended, e.g. the frame it found.
+            let last = &frames[frames.len() - 1];
+            fs::write(path, png::encode(&last.to_colors(options.scale)))
+                .with_context(|| format!("could not write \"{}\"", path.display()))?;
+            if frames.len() > 1 {
+                eprintln!("Note: a PNG holds a single image, only the last frame is kept.");
+            }
+            eprintln!("Wrote the last frame to \"{target}\".");
+            return io::stderr().flush().context("could not write to stderr");
+        }
+        Target::Terminal => {
+            term::play(&frames, options.fps, &mut io::stderr().lock())
+                .context("could not play the frames")?;
+            return Ok(());
+        }
+    }
+
+    eprintln!("Wrote {} frame(s) to \"{target}\".", frames.len());
+    io::stderr().flush().context("could not write to stderr")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Frame, Options, Palette, Recorder, Rgb, Target};
+    use crate::grid::Grid;
+
+    fn recorder(max_frames: usize) -> Recorder {
+        Recorder {
+            target: Target::Terminal,
+            options: Options {
+                fps: 10,
+                scale: 1,
+                max_frames,
+            },
+            frames: vec![],
+            pushed: 0,
+            stride: 1,
+            last: None,
+        }
+    }
+
+    fn frame(n: u8) -> Frame {
+        Frame::Colors(Grid::new(1, 1, Rgb(n, 0, 0)))
+    }
+
+    fn first_color(frame: &Frame) -> u8 {
+        frame.to_colors(1).cells()[0].0
+    }
+
+    #[test]
+    fn parses_targets() {
+        assert_eq!("out.gif".parse(), Ok(Target::Gif("out.gif".into())));
+        assert_eq!("frames/".parse(), Ok(Target::Ppm("frames/".into())));
+        assert_eq!("term".parse(), Ok(Target::Terminal));
+        assert_eq!("tree.png".parse(), Ok(Target::Png("tree.png".into())));
+        assert!("out.jpg".parse::<Target>().is_err());
+        assert_eq!(Target::Ppm("frames".into()).to_string(), "frames/");
+    }
+
+    #[test]
+    fn draws_scaled_frames() {
+        let grid = Grid::parse("#.\n.@");
+        let image = Frame::Chars(grid, Palette::new(&[('@', Rgb::WHITE)])).to_colors(2);
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.row(0)[1], Palette::DEFAULT.color('#'));
+        assert_eq!(image.row(3)[3], Rgb::WHITE);
+        assert_ne!(Palette::DEFAULT.color('a'), Palette::DEFAULT.color('b'));
+    }
+
+    #[test]
+    fn thins_frames_beyond_the_limit() {
+        let mut recorder = recorder(4);
+        for n in 0..10 {
+            recorder.push(frame(n));
+        }
+
+        let kept: Vec<u8> = recorder.frames.iter().map(first_color).collect();
+        assert_eq!(kept, [0, 4, 8]);
+        assert_eq!(recorder.last.as_ref().map(first_color), Some(9));
+    }
+}
@@ -0,0 +1,115 @@
+//! A minimal encoder of PNG images. The pixels are stored uncompressed, which keeps the encoder
+//! small and is fine for the few still images a solution writes.
+use super::Rgb;
+use crate::grid::Grid;
+
+/// The most bytes a stored block of a zlib stream holds.
+const MAX_BLOCK: usize = u16::MAX as usize;
+
+pub(super) fn encode(image: &Grid<Rgb>) -> Vec<u8> {
+    let mut header = vec![];
+    header.extend((image.width() as u32).to_be_bytes());
+    header.extend((image.height() as u32).to_be_bytes());
+    // NOTE: 8-bit truecolor, the default compression and filters, no interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+
+    // NOTE: each row starts with the type of its filter, 0 for none.
+    let mut pixels = Vec::with_capacity((image.width() * 3 + 1) * image.height());
+    for row in image.rows() {
+        pixels.push(0);
+        for Rgb(r, g, b) in row {
+            pixels.extend([*r, *g, *b]);
+        }
+    }
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut out, b"IHDR", &header);
+    chunk(&mut out, b"IDAT", &zlib(&pixels));
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of stored, uncompressed, blocks.
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ u32::from(*byte), |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), byte| {
+        let a = (a + u32::from(*byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{adler32, crc32, encode, zlib};
+    use crate::grid::Grid;
+    use crate::viz::Rgb;
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn stores_blocks() {
+        assert_eq!(
+            zlib(&[]),
+            [0x78, 0x01, 0x01, 0x00, 0x00, 0xff, 0xff, 0, 0, 0, 1]
+        );
+
+        let data = vec![7; 70_000];
+        let stream = zlib(&data);
+        assert_eq!(stream.len(), 2 + 5 + 65535 + 5 + 4465 + 4);
+        assert_eq!(stream[2], 0x00);
+        assert_eq!(stream[2 + 5 + 65535], 0x01);
+    }
+
+    #[test]
+    fn encodes_images() {
+        let image = Grid::from_vec(2, vec![Rgb(1, 2, 3), Rgb::WHITE]);
+        let png = encode(&image);
+
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"));
+        assert_eq!(&png[16..24], [0, 0, 0, 2, 0, 0, 0, 1]);
+        let pixels = [0, 1, 2, 3, 255, 255, 255];
+        assert!(png.windows(pixels.len()).any(|w| w == pixels));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+}
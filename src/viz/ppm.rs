@@ -0,0 +1,26 @@
+//! The binary PPM image format, a header followed by the colors of each pixel.
+use super::Rgb;
+use crate::grid::Grid;
+
+pub(super) fn encode(image: &Grid<Rgb>) -> Vec<u8> {
+    let mut out = format!("P6\n{} {}\n255\n", image.width(), image.height()).into_bytes();
+    for Rgb(r, g, b) in image.cells() {
+        out.extend([*r, *g, *b]);
+    }
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::encode;
+    use crate::grid::Grid;
+    use crate::viz::Rgb;
+
+    #[test]
+    fn encodes_images() {
+        let image = Grid::from_vec(2, vec![Rgb(1, 2, 3), Rgb::WHITE]);
+        assert_eq!(encode(&image), b"P6\n2 1\n255\n\x01\x02\x03\xff\xff\xff");
+    }
+}
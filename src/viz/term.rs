@@ -0,0 +1,83 @@
+//! Plays frames in the terminal, redrawing each one over the previous one.
+use std::{io::Write, thread, time::Duration};
+
+use super::{Frame, Rgb};
+use crate::grid::Point;
+use crate::template::output;
+
+pub(super) fn play(frames: &[Frame], fps: u64, out: &mut impl Write) -> std::io::Result<()> {
+    let delay = Duration::from_secs(1) / u32::try_from(fps.max(1)).unwrap_or(u32::MAX);
+    let mut drawn_lines = 0;
+
+    for (i, frame) in frames.iter().enumerate() {
+        let lines = render(frame, output::color());
+        if drawn_lines > 0 {
+            // NOTE: moves up to the start of the previous frame and clears it.
+            write!(out, "\x1b[{drawn_lines}A\r\x1b[J")?;
+        }
+        for line in &lines {
+            writeln!(out, "{line}")?;
+        }
+        writeln!(out, "frame {}/{}", i + 1, frames.len())?;
+        out.flush()?;
+
+        drawn_lines = lines.len() + 1;
+        if i + 1 < frames.len() {
+            thread::sleep(delay);
+        }
+    }
+    Ok(())
+}
+
+/// Renders a frame as lines of text. Grids of characters are drawn as they are, grids of colors
+/// as half blocks, two rows per line, or as blocks of their brightness without colors.
+fn render(frame: &Frame, color: bool) -> Vec<String> {
+    match frame {
+        Frame::Chars(grid, _) => grid.rows().map(|row| row.iter().collect()).collect(),
+        Frame::Colors(grid) if color => (0..grid.height() as i32)
+            .step_by(2)
+            .map(|y| {
+                let mut line: String = (0..grid.width() as i32)
+                    .map(|x| {
+                        let Rgb(r, g, b) = grid[Point::new(x, y)];
+                        let Rgb(br, bg, bb) =
+                            grid.get(Point::new(x, y + 1)).copied().unwrap_or_default();
+                        format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m▀")
+                    })
+                    .collect();
+                line.push_str("\x1b[0m");
+                line
+            })
+            .collect(),
+        Frame::Colors(grid) => grid
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|color| if color.luminance() >= 128 { '█' } else { ' ' })
+                    .collect()
+            })
+            .collect(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::grid::Grid;
+    use crate::viz::{Frame, Palette, Rgb};
+
+    #[test]
+    fn renders_frames() {
+        let chars = Frame::Chars(Grid::parse("#.\n.@"), Palette::DEFAULT);
+        assert_eq!(render(&chars, true), ["#.", ".@"]);
+
+        let colors = Frame::Colors(Grid::from_vec(2, vec![Rgb::WHITE, Rgb::BLACK]));
+        assert_eq!(render(&colors, false), ["█ "]);
+        assert_eq!(
+            render(&colors, true),
+            ["\x1b[38;2;255;255;255m\x1b[48;2;0;0;0m▀\x1b[38;2;0;0;0m\x1b[48;2;0;0;0m▀\x1b[0m"]
+        );
+    }
+}